@@ -10,7 +10,10 @@ pub struct MockError(pub String);
 pub struct MockCanvas {
     pub shown: i32,
     pub cleared: i32,
-    pub color: Color
+    pub color: Color,
+    pub alpha: u8,
+    /// Every line drawn, with the colour it was drawn in
+    pub lines: Vec<(Pixel, Pixel, Color)>,
 }
 
 impl MockCanvas {
@@ -20,7 +23,9 @@ impl MockCanvas {
             cleared: 0,
             // unlikely to be this by random so we can check
             // if it's changed
-            color: Color(1, 1, 1) 
+            color: Color(1, 1, 1),
+            alpha: 255,
+            lines: Vec::new(),
         }
     }
 }
@@ -39,6 +44,7 @@ impl Canvas for MockCanvas {
 
     /// Should draw a line from `start` to `end`
     fn draw_line<P: Into<Pixel>>(&mut self, start: P, end: P) -> Result<(), Self::Err> {
+        self.lines.push((start.into(), end.into(), self.color));
         Ok(())
     }
     /// Should outline the rect in active color
@@ -80,6 +86,10 @@ impl Canvas for MockCanvas {
     fn set_color<C: Into<Color>>(&mut self, color: C) {
         self.color = color.into();
     }
+
+    fn set_alpha(&mut self, alpha: u8) {
+        self.alpha = alpha;
+    }
 }
 
 impl fmt::Display for MockError {
@@ -7,6 +7,7 @@ pub mod mock_canvas;
 pub mod sdl2;
 
 use pixel::{Pixel, Color};
+use utils;
 
 use std::error::Error;
 
@@ -26,10 +27,24 @@ pub trait Canvas {
     fn draw_rect<P: Into<Pixel>>(&mut self, start: P, width: f64, height: f64) -> Result<(), Self::Err>;
     /// Should fill the rect in active color
     fn fill_rect<P: Into<Pixel>>(&mut self, start: P, width: f64, height: f64) -> Result<(), Self::Err>;
+    /// Should fill the polygon with the given vertices in active color
+    /// by default the polygon is filled one horizontal line at a time
+    fn fill_polygon(&mut self, points: &[Pixel]) -> Result<(), Self::Err> {
+        for (y, x_start, x_end) in utils::scanline_spans(points) {
+            self.draw_line((x_start, y), (x_end, y))?;
+        }
+
+        Ok(())
+    }
 
     /// Should write text starting at bottom_left
     fn write_text<P: Into<Pixel>>(&mut self, t: &str, bottom_left: P) -> Result<(), Self::Err>;
     fn write_text_centred<P: Into<Pixel>>(&mut self, t: &str, centre: P) -> Result<(), Self::Err>;
+    /// Should return the width and height in pixels that `t` takes up when written
+    /// by default this guesses from the amount of characters in `t`
+    fn text_size(&self, t: &str) -> Result<(f64, f64), Self::Err> {
+        Ok((8.0 * (t.chars().count() as f64), 16.0))
+    }
     /// Convenience method to save converting strings to num for axis labels
     fn write_num_centred<P: Into<Pixel>>(&mut self, t: f64, p: P) -> Result<(), Self::Err> {
         let t = format!("{:.1}", t);
@@ -44,4 +59,7 @@ pub trait Canvas {
     /// Should set the currently active color of the canvas
     /// Anything drawn by the canvas should be done in the active color
     fn set_color<C: Into<Color>>(&mut self, color: C);
+    /// Should set the opacity of anything drawn afterwards, 255 is fully opaque
+    /// backends that can't blend colours may ignore this
+    fn set_alpha(&mut self, _alpha: u8) {}
 }
\ No newline at end of file
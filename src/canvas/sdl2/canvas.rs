@@ -1,17 +1,23 @@
 use sdl2;
-use sdl2::render::{Renderer, TextureQuery};
+use sdl2::render::{Renderer, TextureQuery, BlendMode};
 use sdl2::rect::{Point, Rect};
 use sdl2::pixels::Color;
 use sdl2::event::Event;
 use sdl2::mouse::MouseButton;
 use sdl2::ttf::Font;
 use sdl2::keyboard::Keycode;
+use sdl2::EventPump;
 
 use canvas::Canvas;
 use pixel;
-use pixel::Pixel;
+use pixel::{Pixel, GraphCoord};
 use graph_2d::Graph2D;
-use options::AxisOptions;
+use polar_graph::PolarGraph;
+use graph_3d::Graph3D;
+use ternary_graph::{TernaryGraph, TernarySeries};
+use plottable::graphs_3d::Plottable3D;
+use plottable::network::NetworkGraph;
+use options::{AxisOptions, PolarOptions, Graph3DOptions, TernaryOptions};
 use data_set::DataSet;
 use canvas::sdl2::SDL2Error;
 
@@ -39,6 +45,18 @@ impl <'a> SDL2Canvas<'a> {
 
         Pixel::new(p.x, (h - p.y).abs())
     }
+
+    // SDL2 takes the size of a rect as a u32 so a negative width or height
+    // has to be turned into a positive one by moving the start of the rect
+    fn to_rect<P: Into<Pixel>>(&self, start: P, width: f64, height: f64) -> Rect {
+        let start = start.into();
+        let x = if width < 0.0 { start.x + width } else { start.x };
+        let y = if height < 0.0 { start.y + height } else { start.y };
+
+        let Pixel { x, y } = self.convert_to_bottom_left_origin((x, y));
+        let (width, height) = (width.abs(), height.abs());
+        Rect::new(x as i32, (y - height) as i32, width as u32, height as u32)
+    }
 }
 
 impl <'a> Canvas for SDL2Canvas<'a> {
@@ -63,16 +81,14 @@ impl <'a> Canvas for SDL2Canvas<'a> {
     }
 
     fn draw_rect<P: Into<Pixel>>(&mut self, start: P, width: f64, height: f64) -> Result<(), SDL2Error> {
-        let Pixel { x, y } = self.convert_to_bottom_left_origin(start);
-        let rect = Rect::new(x as i32, (y - height) as i32, width as u32, height as u32);
+        let rect = self.to_rect(start, width, height);
         self.renderer.draw_rect(rect)?;
 
         Ok(())
     }
 
     fn fill_rect<P: Into<Pixel>>(&mut self, start: P, width: f64, height: f64) -> Result<(), SDL2Error> {
-        let Pixel { x, y } = self.convert_to_bottom_left_origin(start);
-        let rect = Rect::new(x as i32, (y - height) as i32, width as u32, height as u32);
+        let rect = self.to_rect(start, width, height);
         self.renderer.fill_rect(rect)?;
 
         Ok(())
@@ -104,6 +120,11 @@ impl <'a> Canvas for SDL2Canvas<'a> {
         Ok(())
     }
     
+    fn text_size(&self, t: &str) -> Result<(f64, f64), SDL2Error> {
+        let (width, height) = self.font.size_of(t)?;
+        Ok((width as f64, height as f64))
+    }
+
     fn clear(&mut self) {
         self.renderer.clear();
     }
@@ -114,17 +135,29 @@ impl <'a> Canvas for SDL2Canvas<'a> {
 
     fn set_color<C: Into<pixel::Color>>(&mut self, color: C) {
         let pixel::Color(r, g, b) = color.into();
-        self.renderer.set_draw_color(Color::RGB(r, g, b));
+        // keep whatever alpha was set by set_alpha
+        let a = match self.renderer.draw_color() {
+            Color::RGBA(_, _, _, a) => a,
+            Color::RGB(..) => 255,
+        };
+        self.renderer.set_draw_color(Color::RGBA(r, g, b, a));
+    }
+
+    fn set_alpha(&mut self, alpha: u8) {
+        let (r, g, b) = match self.renderer.draw_color() {
+            Color::RGBA(r, g, b, _) | Color::RGB(r, g, b) => (r, g, b),
+        };
+        let blend = if alpha == 255 { BlendMode::None } else { BlendMode::Blend };
+
+        self.renderer.set_blend_mode(blend);
+        self.renderer.set_draw_color(Color::RGBA(r, g, b, alpha));
     }
 }
 
-/// This is a convenience function
-/// It will construct a window with given width and height
-/// then pass an `SDL2Canvas` to the function given which allows a graph to be
-/// drawn, it will then loop until the window is closed or esc is pressed
-pub fn plot<'a, 'c, 'o, A>(w: u32, h: u32, font_size: u16,
-    data_sets: Vec<&'a DataSet<'a>>, x_opts: A, y_opts: A) -> Result<(), SDL2Error>
-    where A: Into<Option<&'o AxisOptions<'o>>> {
+// Creates a window with an `SDL2Canvas` which is passed to `f` along with
+// the event pump, so `f` can draw a graph and then handle any events
+fn with_canvas<F>(w: u32, h: u32, font_size: u16, f: F) -> Result<(), SDL2Error>
+    where F: FnOnce(&mut SDL2Canvas, &mut EventPump) -> Result<(), SDL2Error> {
 
     let sdl_context = sdl2::init().unwrap();
     let ttf_context = sdl2::ttf::init().unwrap();
@@ -145,64 +178,205 @@ pub fn plot<'a, 'c, 'o, A>(w: u32, h: u32, font_size: u16,
     let font = ttf_context.load_font(Path::new("./Ubuntu-R.ttf"), font_size).unwrap();
     let mut canvas = SDL2Canvas::new(renderer, font);
 
-    let mut graph = Graph2D::with_axises(&mut canvas, data_sets, x_opts, y_opts);
-    graph.show()?;
-
     let mut event_pump = sdl_context.event_pump()?;
+    f(&mut canvas, &mut event_pump)
+}
 
-    let mut prev_x = -1.0;
-    let mut prev_y = -1.0;
-    let mut mouse_down = false;
+// Loops until the window is closed or esc is pressed
+fn wait_for_quit(event_pump: &mut EventPump) {
     'running: loop {
         for event in event_pump.poll_iter() {
             match event {
                 Event::Quit {..} | Event::KeyDown { keycode: Some(Keycode::Escape), .. } => {
                     break 'running
                 },
-                Event::MouseButtonDown { x, y, mouse_btn, .. } => {
-                    match mouse_btn {
-                        MouseButton::Left => {
-                            mouse_down = true;
-                            prev_x = x as f64;
-                            prev_y = y as f64;
-                        },
-                        _ => {},
-                    };
-                },
-                Event::MouseButtonUp { mouse_btn, .. } => {
-                    match mouse_btn {
-                        MouseButton::Left => mouse_down = false,
-                        _ => {},
-                    };
-                },
-                Event::MouseMotion { x, y, .. } => {
-                    if mouse_down {
-                        let x = x as f64;
-                        let y = y as f64;
-                        if prev_x == -1.0 || prev_y == -1.0 {
+                _ => {}
+            }
+        }
+    }
+}
+
+/// This is a convenience function
+/// It will construct a window with given width and height
+/// then pass an `SDL2Canvas` to the function given which allows a graph to be
+/// drawn, it will then loop until the window is closed or esc is pressed
+pub fn plot<'a, 'c, 'o, A>(w: u32, h: u32, font_size: u16,
+    data_sets: Vec<&'a DataSet<'a>>, x_opts: A, y_opts: A) -> Result<(), SDL2Error>
+    where A: Into<Option<&'o AxisOptions<'o>>> {
+
+    let (x_opts, y_opts) = (x_opts.into(), y_opts.into());
+
+    with_canvas(w, h, font_size, |canvas, event_pump| {
+        let mut graph = Graph2D::with_axises(canvas, data_sets, x_opts, y_opts);
+        graph.show()?;
+
+        let mut prev_x = -1.0;
+        let mut prev_y = -1.0;
+        let mut mouse_down = false;
+        'running: loop {
+            for event in event_pump.poll_iter() {
+                match event {
+                    Event::Quit {..} | Event::KeyDown { keycode: Some(Keycode::Escape), .. } => {
+                        break 'running
+                    },
+                    Event::MouseButtonDown { x, y, mouse_btn, .. } => {
+                        match mouse_btn {
+                            MouseButton::Left => {
+                                mouse_down = true;
+                                prev_x = x as f64;
+                                prev_y = y as f64;
+                            },
+                            _ => {},
+                        };
+                    },
+                    Event::MouseButtonUp { mouse_btn, .. } => {
+                        match mouse_btn {
+                            MouseButton::Left => mouse_down = false,
+                            _ => {},
+                        };
+                    },
+                    Event::MouseMotion { x, y, .. } => {
+                        if mouse_down {
+                            let x = x as f64;
+                            let y = y as f64;
+                            if prev_x == -1.0 || prev_y == -1.0 {
+                                prev_x = x;
+                                prev_y = y;
+                                continue 'running;
+                            }
+                            let curr_coord = graph.dimensions.convert_to_graphcoord((x, y));
+                            let prev_coord = graph.dimensions.convert_to_graphcoord((prev_x, prev_y));
+
+                            match (curr_coord, prev_coord) {
+                                (Some(_), Some(_)) => {
+                                    let delta = graph.dimensions.distance_travelled_to_relative((prev_x - x, y - prev_y));
+                                    graph.move_view(delta.x, delta.y)?;
+                                },
+                                _ => {},
+                            }
+
                             prev_x = x;
                             prev_y = y;
-                            continue 'running;
                         }
-                        let curr_coord = graph.dimensions.convert_to_graphcoord((x, y));
-                        let prev_coord = graph.dimensions.convert_to_graphcoord((prev_x, prev_y));
+                    },
+                    _ => {}
+                }
+            }
+        }
 
-                        match (curr_coord, prev_coord) {
-                            (Some(_), Some(_)) => {
-                                let delta = graph.dimensions.distance_travelled_to_relative((prev_x - x, y - prev_y));
-                                graph.move_view(delta.x, delta.y)?;
-                            },
-                            _ => {},
+        Ok(())
+    })
+}
+
+/// Same as `plot` except the data sets are drawn on a `PolarGraph`
+pub fn plot_polar<'a, 'o>(w: u32, h: u32, font_size: u16,
+    data_sets: Vec<&'a DataSet<'a>>, opts: &'o PolarOptions<'o>) -> Result<(), SDL2Error> {
+
+    with_canvas(w, h, font_size, |canvas, event_pump| {
+        let mut graph = PolarGraph::new(canvas, data_sets, opts);
+        graph.show()?;
+
+        wait_for_quit(event_pump);
+        Ok(())
+    })
+}
+
+/// Same as `plot` except the series are drawn on a `TernaryGraph`
+pub fn plot_ternary<'a, 'o>(w: u32, h: u32, font_size: u16,
+    series: Vec<&'a TernarySeries<'a>>, opts: &'o TernaryOptions<'o>) -> Result<(), SDL2Error> {
+
+    with_canvas(w, h, font_size, |canvas, event_pump| {
+        let mut graph = TernaryGraph::new(canvas, series, opts);
+        graph.show()?;
+
+        wait_for_quit(event_pump);
+        Ok(())
+    })
+}
+
+/// Same as `plot` except the series are drawn on a `Graph3D`, dragging
+/// with the left mouse button rotates the view instead of moving it
+pub fn plot_3d<'a, 'o>(w: u32, h: u32, font_size: u16,
+    series: Vec<&'a (dyn Plottable3D + 'a)>, opts: &'o Graph3DOptions<'o>) -> Result<(), SDL2Error> {
+
+    with_canvas(w, h, font_size, |canvas, event_pump| {
+        let mut graph = Graph3D::new(canvas, series, opts);
+        graph.show()?;
+
+        // the amount of degrees the view is turned for each pixel dragged
+        let degrees_per_pixel = 0.5;
+        let mut prev = None;
+        'running: loop {
+            for event in event_pump.poll_iter() {
+                match event {
+                    Event::Quit {..} | Event::KeyDown { keycode: Some(Keycode::Escape), .. } => {
+                        break 'running
+                    },
+                    Event::MouseButtonDown { x, y, mouse_btn: MouseButton::Left, .. } => {
+                        prev = Some((x as f64, y as f64));
+                    },
+                    Event::MouseButtonUp { mouse_btn: MouseButton::Left, .. } => {
+                        prev = None;
+                    },
+                    Event::MouseMotion { x, y, .. } => {
+                        if let Some((prev_x, prev_y)) = prev {
+                            let (x, y) = (x as f64, y as f64);
+                            graph.rotate((prev_x - x) * degrees_per_pixel, (y - prev_y) * degrees_per_pixel)?;
+                            prev = Some((x, y));
                         }
+                    },
+                    _ => {}
+                }
+            }
+        }
 
-                        prev_x = x;
-                        prev_y = y;
-                    }
-                },
-                _ => {}
+        Ok(())
+    })
+}
+
+/// Draws a `NetworkGraph` filling the window, any node can be dragged to
+/// a new position with the left mouse button
+pub fn plot_network(w: u32, h: u32, font_size: u16, network: &mut NetworkGraph) -> Result<(), SDL2Error> {
+    with_canvas(w, h, font_size, |canvas, event_pump| {
+        let (width, height) = canvas.get_size();
+
+        // the view stays the same while dragging, so nodes don't jump around
+        let dims = network.dimensions(width, height);
+        network.show(&dims, canvas)?;
+
+        let mut dragging = None;
+        'running: loop {
+            for event in event_pump.poll_iter() {
+                match event {
+                    Event::Quit {..} | Event::KeyDown { keycode: Some(Keycode::Escape), .. } => {
+                        break 'running
+                    },
+                    Event::MouseButtonDown { x, y, mouse_btn: MouseButton::Left, .. } => {
+                        let (x, y) = (x as f64, y as f64);
+                        dragging = network.node_at(&dims, Pixel::new(x, height - y)).map(|node| (node, x, y));
+                    },
+                    Event::MouseButtonUp { mouse_btn: MouseButton::Left, .. } => {
+                        dragging = None;
+                    },
+                    Event::MouseMotion { x, y, .. } => {
+                        if let Some((node, prev_x, prev_y)) = dragging {
+                            let (x, y) = (x as f64, y as f64);
+                            let delta = dims.distance_travelled_to_relative((x - prev_x, prev_y - y));
+                            let position = network.positions()[node];
+
+                            // the node is kept on the graph so it can't be dragged out of reach
+                            let moved = GraphCoord::new((position.x + delta.x).clamp(dims.min.x, dims.max.x),
+                                                        (position.y + delta.y).clamp(dims.min.y, dims.max.y));
+                            network.move_node(node, moved);
+                            network.show(&dims, canvas)?;
+                            dragging = Some((node, x, y));
+                        }
+                    },
+                    _ => {}
+                }
             }
         }
-    }
 
-    Ok(())
-}
\ No newline at end of file
+        Ok(())
+    })
+}
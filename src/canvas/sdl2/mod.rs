@@ -2,5 +2,5 @@ mod canvas;
 mod error;
 
 pub use self::canvas::SDL2Canvas;
-pub use self::canvas::plot;
+pub use self::canvas::{plot, plot_polar, plot_3d, plot_ternary, plot_network};
 pub use self::error::SDL2Error;
\ No newline at end of file
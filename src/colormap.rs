@@ -0,0 +1,97 @@
+//! This module defines `Colormap`, which is used by plots that need to turn
+//! a value into a colour, such as a filled contour or a density plot
+
+use pixel::Color;
+
+/// A `Colormap` maps a value between 0 and 1 onto a `Color`
+/// by linearly interpolating between a fixed set of colours
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum Colormap {
+    #[default]
+    Viridis,
+    Grayscale,
+    Heat,
+    BlueRed,
+}
+
+const VIRIDIS: &[Color] = &[
+    Color(68, 1, 84), Color(59, 82, 139), Color(33, 145, 140),
+    Color(94, 201, 98), Color(253, 231, 37),
+];
+const GRAYSCALE: &[Color] = &[Color(0, 0, 0), Color(255, 255, 255)];
+const HEAT: &[Color] = &[
+    Color(0, 0, 0), Color(230, 0, 0), Color(255, 210, 0), Color(255, 255, 255),
+];
+const BLUE_RED: &[Color] = &[Color(0, 0, 255), Color(255, 255, 255), Color(255, 0, 0)];
+
+/// Distinct colours for things that have no order, such as categories,
+/// anything past the end of it should start again from the beginning
+pub const PALETTE: &[Color] = &[
+    Color(78, 121, 167), Color(242, 142, 43), Color(225, 87, 89), Color(118, 183, 178),
+    Color(89, 161, 79), Color(237, 201, 72), Color(176, 122, 161), Color(156, 117, 95),
+];
+
+impl Colormap {
+    fn stops(&self) -> &'static [Color] {
+        match *self {
+            Colormap::Viridis => VIRIDIS,
+            Colormap::Grayscale => GRAYSCALE,
+            Colormap::Heat => HEAT,
+            Colormap::BlueRed => BLUE_RED,
+        }
+    }
+
+    /// Gets the colour at `t` where `t` should be between 0 and 1,
+    /// anything outside that range is clamped to the nearest end
+    pub fn color_at(&self, t: f64) -> Color {
+        let stops = self.stops();
+        let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+
+        let scaled = t * ((stops.len() - 1) as f64);
+        let i = (scaled.floor() as usize).min(stops.len() - 2);
+        let frac = scaled - (i as f64);
+
+        let Color(r1, g1, b1) = stops[i];
+        let Color(r2, g2, b2) = stops[i + 1];
+        let lerp = |a: u8, b: u8| ((a as f64) + frac * ((b as f64) - (a as f64))).round() as u8;
+
+        Color(lerp(r1, r2), lerp(g1, g2), lerp(b1, b2))
+    }
+
+    /// Convenience method which scales `value` from the range `min` to `max`
+    /// before picking a colour
+    pub fn color_for(&self, value: f64, min: f64, max: f64) -> Color {
+        if max <= min {
+            return self.color_at(0.5);
+        }
+        self.color_at((value - min) / (max - min))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pixel::Color;
+
+    #[test]
+    fn test_color_at() {
+        let map = Colormap::Grayscale;
+
+        assert_eq!(map.color_at(0.0), Color(0, 0, 0));
+        assert_eq!(map.color_at(1.0), Color(255, 255, 255));
+        assert_eq!(map.color_at(0.5), Color(128, 128, 128));
+
+        // values outside of 0 to 1 should be clamped
+        assert_eq!(map.color_at(-3.0), Color(0, 0, 0));
+        assert_eq!(map.color_at(7.0), Color(255, 255, 255));
+    }
+
+    #[test]
+    fn test_color_for() {
+        let map = Colormap::BlueRed;
+
+        assert_eq!(map.color_for(-10.0, -10.0, 10.0), Color(0, 0, 255));
+        assert_eq!(map.color_for(0.0, -10.0, 10.0), Color(255, 255, 255));
+        assert_eq!(map.color_for(10.0, -10.0, 10.0), Color(255, 0, 0));
+    }
+}
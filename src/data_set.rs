@@ -1,15 +1,40 @@
 use pixel::{GraphCoord, Color};
-use options::DataSetOptions;
+use options::{DataSetOptions, PlotStyle, Orientation};
 use utils;
+use sampler;
 use rand;
 use rand::Rng;
 
+/// `ErrorBar` is the uncertainty of a single value, `minus` is how far
+/// the error bar extends below the value and `plus` is how far it extends above
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ErrorBar {
+    pub minus: f64,
+    pub plus: f64,
+}
+
+impl ErrorBar {
+    pub fn new(minus: f64, plus: f64) -> ErrorBar {
+        ErrorBar { minus: minus, plus: plus }
+    }
+
+    /// An error bar that extends the same distance above and below the value
+    pub fn symmetric(error: f64) -> ErrorBar {
+        ErrorBar::new(error, error)
+    }
+}
+
 /// `DataSet` represents a series on a 2D graph
 /// `DataSet` holds a vector of x and y co-ordinates for a graph
 /// as well as a series of options such as the colour to draw the line
+/// It can also hold the uncertainty of each x and y value
+/// and a size for each point, which is used as the area of a bubble
 pub struct DataSet<'a> {
     pub data_points: Vec<GraphCoord>,
     pub options: &'a DataSetOptions<'a>,
+    pub x_errors: Option<Vec<ErrorBar>>,
+    pub y_errors: Option<Vec<ErrorBar>>,
+    pub sizes: Option<Vec<f64>>,
 }
 
 impl <'a> DataSet<'a> {
@@ -17,6 +42,9 @@ impl <'a> DataSet<'a> {
         DataSet {
             data_points: data_points,
             options: opts,
+            x_errors: None,
+            y_errors: None,
+            sizes: None,
         }
     }
 
@@ -39,6 +67,80 @@ impl <'a> DataSet<'a> {
         DataSet::from_vecs(x, y, opts).expect("y.len() must equal x.len()")
     }
 
+    /// Like `from_fn` but chooses the x co-ordinates between `x_min` and `x_max`
+    /// itself, taking more samples wherever the curve bends and leaving a gap
+    /// anywhere the function jumps, such as the asymptotes of tan
+    ///
+    /// The samples are only as good as they need to be for a graph of the whole range,
+    /// use a `FunctionSeries` to resample each time the view is panned or zoomed
+    pub fn from_fn_adaptive<F>(x_min: f64, x_max: f64, opts: &'a DataSetOptions<'a>, f: F) -> Self
+        where F: Fn(f64) -> f64 {
+
+        let bounds = sampler::default_bounds(&f, x_min, x_max);
+        DataSet::new(sampler::sample(&f, x_min, x_max, &bounds), opts)
+    }
+
+    /// Takes a vector of values for the parameter t and uses the functions
+    /// fx and fy to create a `GraphCoord` at (fx(t), fy(t)) for each one
+    pub fn from_parametric<Fx, Fy>(t: Vec<f64>, opts: &'a DataSetOptions<'a>, fx: Fx, fy: Fy) -> Self
+        where Fx: Fn(f64) -> f64, Fy: Fn(f64) -> f64 {
+
+        let points = t.into_iter().map(|t| GraphCoord::new(fx(t), fy(t))).collect::<Vec<_>>();
+        DataSet::new(points, opts)
+    }
+
+    /// Takes a vector of angles in radians and uses the function r to create a
+    /// `GraphCoord` at the point that is r(theta) away from the origin at each angle
+    pub fn from_polar<F>(theta: Vec<f64>, opts: &'a DataSetOptions<'a>, r: F) -> Self
+        where F: Fn(f64) -> f64 {
+
+        let points = theta.into_iter().map(|t| {
+            let r = r(t);
+            GraphCoord::new(r * t.cos(), r * t.sin())
+        }).collect::<Vec<_>>();
+        DataSet::new(points, opts)
+    }
+
+    /// Adds an error bar for the x value of each point
+    /// returns `None` if there isn't exactly one error bar for each point
+    pub fn x_errors(mut self, errors: Vec<ErrorBar>) -> Option<Self> {
+        if errors.len() != self.data_points.len() {
+            return None;
+        }
+
+        self.x_errors = Some(errors);
+        Some(self)
+    }
+
+    /// Adds an error bar for the y value of each point
+    /// returns `None` if there isn't exactly one error bar for each point
+    pub fn y_errors(mut self, errors: Vec<ErrorBar>) -> Option<Self> {
+        if errors.len() != self.data_points.len() {
+            return None;
+        }
+
+        self.y_errors = Some(errors);
+        Some(self)
+    }
+
+    /// Adds a size for each point, for a bubble graph this is the area of the bubble
+    /// returns `None` if there isn't exactly one size for each point
+    pub fn sizes(mut self, sizes: Vec<f64>) -> Option<Self> {
+        if sizes.len() != self.data_points.len() {
+            return None;
+        }
+
+        self.sizes = Some(sizes);
+        Some(self)
+    }
+
+    /// The largest size of any point, or 0 if there are no sizes
+    pub fn get_max_size(&self) -> f64 {
+        match self.sizes {
+            Some(ref sizes) => sizes.iter().filter(|s| s.is_finite()).fold(0.0, |acc: f64, &s| acc.max(s)),
+            None => 0.0,
+        }
+    }
 
     /// Gets the maximum value of x and maximum value of y
     /// and returns them as a `GraphCoord`
@@ -50,6 +152,56 @@ impl <'a> DataSet<'a> {
         utils::get_min_coord(&self.data_points)
     }
 
+    /// Like `get_max_coord` but also includes anything else that is drawn
+    /// for this `DataSet` such as error bars or the baseline of an area, bar or stem graph
+    pub fn get_max_extent(&self) -> GraphCoord {
+        self.get_extent().1
+    }
+
+    pub fn get_min_extent(&self) -> GraphCoord {
+        self.get_extent().0
+    }
+
+    fn get_extent(&self) -> (GraphCoord, GraphCoord) {
+        let (min, max) = self.get_error_extent();
+        let opts = self.options;
+
+        match opts.plot_style {
+            PlotStyle::Area | PlotStyle::Stem => (GraphCoord::new(min.x, min.y.min(opts.baseline)),
+                                GraphCoord::new(max.x, max.y.max(opts.baseline))),
+            PlotStyle::Bar => {
+                let half_width = opts.bar_width / 2.0;
+                let (low, high) = (min.y.min(opts.baseline), max.y.max(opts.baseline));
+
+                match opts.orientation {
+                    Orientation::Vertical => (GraphCoord::new(min.x - half_width, low),
+                                              GraphCoord::new(max.x + half_width, high)),
+                    Orientation::Horizontal => (GraphCoord::new(low, min.x - half_width),
+                                                GraphCoord::new(high, max.x + half_width)),
+                }
+            },
+            _ => (min, max),
+        }
+    }
+
+    // the extremes of the points once the error bars have been added on
+    fn get_error_extent(&self) -> (GraphCoord, GraphCoord) {
+        let mut ends = self.data_points.clone();
+
+        for (n, p) in self.data_points.iter().enumerate() {
+            if let Some(e) = self.x_errors.as_ref().and_then(|errors| errors.get(n)) {
+                ends.push(GraphCoord::new(p.x - e.minus, p.y));
+                ends.push(GraphCoord::new(p.x + e.plus, p.y));
+            }
+            if let Some(e) = self.y_errors.as_ref().and_then(|errors| errors.get(n)) {
+                ends.push(GraphCoord::new(p.x, p.y - e.minus));
+                ends.push(GraphCoord::new(p.x, p.y + e.plus));
+            }
+        }
+
+        (utils::get_min_coord(&ends), utils::get_max_coord(&ends))
+    }
+
     /// Using the options specified in the options field
     /// chooses the color that should be used to draw this
     /// `DataSet`
@@ -64,4 +216,49 @@ impl <'a> DataSet<'a> {
             }
         }    
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use options::DataSetOptions;
+    use pixel::GraphCoord;
+    use std::f64::consts::PI;
+
+    fn assert_close(points: &[GraphCoord], expected: &[(f64, f64)]) {
+        assert_eq!(points.len(), expected.len());
+        for (p, &(x, y)) in points.iter().zip(expected.iter()) {
+            assert!((p.x - x).abs() < 1e-9 && (p.y - y).abs() < 1e-9, "{:?} is not ({}, {})", p, x, y);
+        }
+    }
+
+    #[test]
+    fn test_from_fn() {
+        let opts = DataSetOptions::default();
+        let ds = DataSet::from_fn(vec!(0.0, 1.0, 2.0), &opts, |x| x * x);
+        assert_eq!(ds.data_points, vec!(GraphCoord::new(0.0, 0.0), GraphCoord::new(1.0, 1.0), GraphCoord::new(2.0, 4.0)));
+    }
+
+    #[test]
+    fn test_from_parametric() {
+        let opts = DataSetOptions::default();
+        let t = vec!(0.0, PI / 2.0, PI, 3.0 * PI / 2.0);
+
+        // a circle of radius 2 around (1, 0)
+        let ds = DataSet::from_parametric(t, &opts, |t| 1.0 + 2.0 * t.cos(), |t| 2.0 * t.sin());
+        assert_close(&ds.data_points, &[(3.0, 0.0), (1.0, 2.0), (-1.0, 0.0), (1.0, -2.0)]);
+    }
+
+    #[test]
+    fn test_from_polar() {
+        let opts = DataSetOptions::default();
+        let theta = vec!(0.0, PI / 2.0, PI, 3.0 * PI / 2.0);
+
+        let circle = DataSet::from_polar(theta.clone(), &opts, |_| 3.0);
+        assert_close(&circle.data_points, &[(3.0, 0.0), (0.0, 3.0), (-3.0, 0.0), (0.0, -3.0)]);
+
+        // an archimedean spiral moves further out at every angle
+        let spiral = DataSet::from_polar(theta, &opts, |t| t);
+        assert_close(&spiral.data_points, &[(0.0, 0.0), (0.0, PI / 2.0), (-PI, 0.0), (0.0, -3.0 * PI / 2.0)]);
+    }
+}
@@ -0,0 +1,191 @@
+use pixel::{GraphCoord3D, Pixel};
+use options::Projection;
+use std::f64;
+
+// How far the viewer is from the centre of the box for a perspective projection
+// measured in half widths of the box
+const VIEW_DISTANCE: f64 = 5.0;
+
+/// `Dimensions3D` projects a `GraphCoord3D` onto a `Pixel`
+///
+/// The box between `min` and `max` is scaled to a cube, turned `azimuth` degrees
+/// around the z axis and tilted so it is seen from `elevation` degrees above,
+/// the cube is then centred on the canvas
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Dimensions3D {
+    pub max: GraphCoord3D,
+    pub min: GraphCoord3D,
+
+    pub height: f64,
+    pub width: f64,
+
+    pub border: f64,
+
+    pub azimuth: f64,
+    pub elevation: f64,
+    pub projection: Projection,
+}
+
+impl Dimensions3D {
+    pub fn new(width: f64, height: f64) -> Dimensions3D {
+        Dimensions3D {
+            max: GraphCoord3D::new(f64::MIN, f64::MIN, f64::MIN),
+            min: GraphCoord3D::new(f64::MAX, f64::MAX, f64::MAX),
+
+            height: height,
+            width: width,
+
+            border: 0.1,
+
+            azimuth: -60.0,
+            elevation: 30.0,
+            projection: Projection::Orthographic,
+        }
+    }
+
+    /// Grows the dimensions so that everything between `min` and `max` is inside the box
+    pub fn adjust_for_extent(&mut self, min: GraphCoord3D, max: GraphCoord3D) {
+        let pick = |a: f64, b: f64, f: fn(f64, f64) -> f64| if b.is_finite() { f(a, b) } else { a };
+
+        self.min = GraphCoord3D::new(pick(self.min.x, min.x, f64::min), pick(self.min.y, min.y, f64::min),
+                                     pick(self.min.z, min.z, f64::min));
+        self.max = GraphCoord3D::new(pick(self.max.x, max.x, f64::max), pick(self.max.y, max.y, f64::max),
+                                     pick(self.max.z, max.z, f64::max));
+    }
+
+    /// Turns the view by the given amount of degrees, the elevation is kept
+    /// between looking straight down and straight up
+    pub fn rotate(&mut self, azimuth: f64, elevation: f64) {
+        self.azimuth = (self.azimuth + azimuth) % 360.0;
+        self.elevation = (self.elevation + elevation).clamp(-90.0, 90.0);
+    }
+
+    pub fn centre(&self) -> Pixel {
+        Pixel::new(self.width / 2.0, self.height / 2.0)
+    }
+
+    // Scales each co-ordinate to between -1 and 1
+    fn normalise(&self, p: GraphCoord3D) -> (f64, f64, f64) {
+        let scale = |v: f64, min: f64, max: f64| {
+            if max > min { 2.0 * (v - min) / (max - min) - 1.0 } else { 0.0 }
+        };
+
+        (scale(p.x, self.min.x, self.max.x), scale(p.y, self.min.y, self.max.y), scale(p.z, self.min.z, self.max.z))
+    }
+
+    /// The position on the screen of `p` relative to the centre, in half widths
+    /// of the box, and its depth, where a larger depth is further away
+    pub fn view_position(&self, p: GraphCoord3D) -> (f64, f64, f64) {
+        let (x, y, z) = self.normalise(p);
+        let (azimuth, elevation) = (self.azimuth.to_radians(), self.elevation.to_radians());
+
+        // turn around the z axis, then tilt the far side of the box up
+        let (x, y) = (x * azimuth.cos() - y * azimuth.sin(), x * azimuth.sin() + y * azimuth.cos());
+        let screen_y = z * elevation.cos() + y * elevation.sin();
+        let depth = y * elevation.cos() - z * elevation.sin();
+
+        (x, screen_y, depth)
+    }
+
+    /// Converts `p` to a `Pixel` along with its depth, which is larger for anything
+    /// further away, returns `None` if any co-ordinate of `p` isn't finite
+    pub fn convert_to_pixel<G: Into<GraphCoord3D>>(&self, p: G) -> Option<(Pixel, f64)> {
+        let p = p.into();
+        if !(p.x.is_finite() && p.y.is_finite() && p.z.is_finite()) {
+            return None;
+        }
+
+        let (x, y, depth) = self.view_position(p);
+
+        // every corner of the cube is within sqrt(3) of the centre so it always fits
+        let radius = self.width.min(self.height) * (0.5 - self.border);
+        let mut scale = radius / 3f64.sqrt();
+        if self.projection == Projection::Perspective {
+            // shrink everything so the nearest corner still fits
+            let nearest = VIEW_DISTANCE / (VIEW_DISTANCE - 3f64.sqrt());
+            scale *= VIEW_DISTANCE / (VIEW_DISTANCE + depth) / nearest;
+        }
+
+        let centre = self.centre();
+        Some((Pixel::new(centre.x + x * scale, centre.y + y * scale), depth))
+    }
+
+    /// The eight corners of the box
+    pub fn corners(&self) -> Vec<GraphCoord3D> {
+        let (min, max) = (self.min, self.max);
+        let mut corners = Vec::with_capacity(8);
+
+        for &x in &[min.x, max.x] {
+            for &y in &[min.y, max.y] {
+                for &z in &[min.z, max.z] {
+                    corners.push(GraphCoord3D::new(x, y, z));
+                }
+            }
+        }
+
+        corners
+    }
+
+    /// The corner of the box nearest to the viewer
+    pub fn nearest_corner(&self) -> GraphCoord3D {
+        let corners = self.corners();
+        corners.iter().cloned()
+            .fold((corners[0], f64::MAX), |(nearest, min), c| {
+                let depth = self.view_position(c).2;
+                if depth < min { (c, depth) } else { (nearest, min) }
+            }).0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use options::Projection;
+    use pixel::{GraphCoord3D, Pixel};
+
+    fn assert_close(a: Pixel, b: Pixel) {
+        assert!((a.x - b.x).abs() < 1e-9 && (a.y - b.y).abs() < 1e-9, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_convert_to_pixel() {
+        let mut dims = Dimensions3D::new(600.0, 600.0);
+        dims.adjust_for_extent(GraphCoord3D::new(-1.0, -1.0, -1.0), GraphCoord3D::new(1.0, 1.0, 1.0));
+
+        // the centre of the box is always the centre of the canvas
+        let (pix, depth) = dims.convert_to_pixel((0.0, 0.0, 0.0)).unwrap();
+        assert_close(pix, Pixel::new(300.0, 300.0));
+        assert_eq!(depth, 0.0);
+
+        // looking straight at the x-z plane, x goes right and z goes up
+        dims.azimuth = 0.0;
+        dims.elevation = 0.0;
+        let scale = 240.0 / 3f64.sqrt();
+        assert_close(dims.convert_to_pixel((1.0, 0.0, 0.0)).unwrap().0, Pixel::new(300.0 + scale, 300.0));
+        assert_close(dims.convert_to_pixel((0.0, 0.0, 1.0)).unwrap().0, Pixel::new(300.0, 300.0 + scale));
+        assert!(dims.convert_to_pixel((0.0, 1.0, 0.0)).unwrap().1 > 0.0);
+
+        // with perspective something further away is closer to the centre
+        dims.projection = Projection::Perspective;
+        let near = dims.convert_to_pixel((1.0, -1.0, 0.0)).unwrap().0;
+        let far = dims.convert_to_pixel((1.0, 1.0, 0.0)).unwrap().0;
+        assert!(near.x > far.x);
+
+        assert_eq!(dims.convert_to_pixel((f64::NAN, 0.0, 0.0)), None);
+    }
+
+    #[test]
+    fn test_rotate() {
+        let mut dims = Dimensions3D::new(600.0, 600.0);
+        dims.adjust_for_extent(GraphCoord3D::new(0.0, 0.0, 0.0), GraphCoord3D::new(1.0, 1.0, 1.0));
+        dims.azimuth = 0.0;
+        dims.elevation = 30.0;
+
+        // looking from the front and above, the nearest corner is at the top of the front
+        let nearest = dims.nearest_corner();
+        assert_eq!((nearest.y, nearest.z), (0.0, 1.0));
+
+        dims.rotate(10.0, 80.0);
+        assert_eq!((dims.azimuth, dims.elevation), (10.0, 90.0));
+    }
+}
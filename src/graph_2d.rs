@@ -1,18 +1,27 @@
 use data_set::DataSet;
 use options::{PlotStyle, AxisOptions};
-use plottable::{Plottable, Axis, Legend};
-use plottable::graphs::{LineSeries, ScatterSeries, BarSeries};
-use pixel::Color;
+use plottable::{Plottable, Axis, Category, Legend, LegendEntry};
+use plottable::graphs::{LineSeries, ScatterSeries, BarSeries, AreaSeries, StepSeries, StemSeries, BubbleSeries};
+use pixel::{Color, GraphCoord};
 use graph_dimensions::GraphDimensions;
 use canvas::Canvas;
 
+// `Plottable::plot` is generic over the canvas so it can't be boxed as a trait object,
+// however the canvas is fixed for a `Graph2D` so we can box a closure that plots it instead
+type PlotFn<'a, T> = Box<dyn Fn(&GraphDimensions, &mut T) -> Result<(), <T as Canvas>::Err> + 'a>;
+
 /// A `Graph2D` is a graph with a standard 2d canvas, i.e. a bar, line or a scatter graph
-pub struct Graph2D<'a, 'c, 'o, T: 'c> {
+pub struct Graph2D<'a, 'c, 'o, T: Canvas + 'c> {
     pub data_sets: Vec<&'a DataSet<'a>>,
     canvas: &'c mut T,
     pub dimensions: GraphDimensions,
     x_opts: Option<&'o AxisOptions<'o>>,
     y_opts: Option<&'o AxisOptions<'o>>,
+    plottables: Vec<PlotFn<'a, T>>,
+    extents: Vec<(GraphCoord, GraphCoord)>,
+    legend_entries: Vec<LegendEntry<'a>>,
+    x_categories: Vec<(f64, Category<'a>)>,
+    y_categories: Vec<(f64, Category<'a>)>,
 }
 
 impl <'a, 'c, 'o, T: Canvas> Graph2D<'a, 'c, 'o, T> {
@@ -31,6 +40,11 @@ impl <'a, 'c, 'o, T: Canvas> Graph2D<'a, 'c, 'o, T> {
             dimensions: dimensions,
             x_opts: x_opts.into(),
             y_opts: y_opts.into(),
+            plottables: Vec::new(),
+            extents: Vec::new(),
+            legend_entries: Vec::new(),
+            x_categories: Vec::new(),
+            y_categories: Vec::new(),
         }
     }
 
@@ -39,11 +53,29 @@ impl <'a, 'c, 'o, T: Canvas> Graph2D<'a, 'c, 'o, T> {
         self.data_sets.push(data_set);
     }
 
+    /// Adds anything `Plottable` to the graph, such as a `ContourSeries`
+    /// it will be drawn after the data sets each time the graph is redrawn
+    pub fn add_plottable<P: Plottable + 'a>(&mut self, p: &'a P) {
+        if let Some((min, max)) = p.data_extent() {
+            self.dimensions.adjust_for_extent(min, max);
+            self.extents.push((min, max));
+        }
+        self.legend_entries.extend(p.legend_entries());
+        self.x_categories.extend(p.x_categories());
+        self.y_categories.extend(p.y_categories());
+
+        self.plottables.push(Box::new(move |bounds, canvas| p.plot(bounds, canvas)));
+    }
+
     pub fn plot_data_set(&mut self, ds: &'a DataSet) -> Result<(), T::Err> {
         match ds.options.plot_style {
             PlotStyle::Line => self.plot(&LineSeries(ds)),
             PlotStyle::Bar => self.plot(&BarSeries(ds)),
             PlotStyle::Scatter => self.plot(&ScatterSeries(ds)),
+            PlotStyle::Area => self.plot(&AreaSeries(ds)),
+            PlotStyle::Step(mode) => self.plot(&StepSeries(ds, mode)),
+            PlotStyle::Stem => self.plot(&StemSeries(ds)),
+            PlotStyle::Bubble => self.plot(&BubbleSeries(ds)),
         }
     }
 
@@ -53,6 +85,9 @@ impl <'a, 'c, 'o, T: Canvas> Graph2D<'a, 'c, 'o, T> {
 
     pub fn fit_view_to_data(&mut self) {
         self.dimensions = GraphDimensions::from(self.canvas, &self.data_sets);
+        for &(min, max) in self.extents.iter() {
+            self.dimensions.adjust_for_extent(min, max);
+        }
     }
 
     fn redraw_data_sets(&mut self, prettify_axises: bool) -> Result<(), T::Err> {
@@ -62,9 +97,12 @@ impl <'a, 'c, 'o, T: Canvas> Graph2D<'a, 'c, 'o, T> {
         // We create a new axis each time show is called because axis can't be stored on Graph2D and plotted
         // without cloning it anyway because you would have borrow self mutably
         // to plot axis and borrow self.axis at the same
+        // An axis that shouldn't be drawn is hidden in its `AxisOptions`
+        let (x_categories, y_categories) = (self.x_categories.clone(), self.y_categories.clone());
         match (self.x_opts, self.y_opts) {
             (Some(x_opts), Some(y_opts)) => {
-                let axis = Axis::from_dimensions(&self.dimensions, x_opts, y_opts);
+                let axis = Axis::from_dimensions(&self.dimensions, x_opts, y_opts)
+                    .categories(&x_categories, &y_categories);
                 if prettify_axises {
                     self.dimensions.adjust_for_axis(&axis);
                 }    
@@ -78,7 +116,12 @@ impl <'a, 'c, 'o, T: Canvas> Graph2D<'a, 'c, 'o, T> {
             self.plot_data_set(ds)?;
         } 
 
-        self.plot(&Legend(&data_sets))?;
+        for p in self.plottables.iter() {
+            p(&self.dimensions, self.canvas)?;
+        }
+
+        let legend_entries = self.legend_entries.clone();
+        self.plot(&Legend(&data_sets, &legend_entries))?;
 
         self.canvas.show();
         Ok(())
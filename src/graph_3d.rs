@@ -0,0 +1,296 @@
+use options::{Graph3DOptions, PointStyle};
+use plottable::graphs_3d::{Plottable3D, Shape3D};
+use plottable::primitives::Point;
+use labeller::{Labeller, Label};
+use pixel::{Color, GraphCoord3D, Pixel};
+use dimensions_3d::Dimensions3D;
+use canvas::Canvas;
+use std::cmp::Ordering;
+
+// How far in pixels the tick labels and axis labels are from the box
+const TICK_OFFSET: f64 = 20.0;
+const LABEL_OFFSET: f64 = 45.0;
+
+/// A `Graph3D` draws each `Plottable3D` series inside of a box, with ticks
+/// along three of its edges, projected onto a `Canvas` from any angle
+pub struct Graph3D<'a, 'c, 'o, T: Canvas + 'c> {
+    pub series: Vec<&'a (dyn Plottable3D + 'a)>,
+    canvas: &'c mut T,
+    pub dimensions: Dimensions3D,
+    opts: &'o Graph3DOptions<'o>,
+}
+
+impl <'a, 'c, 'o, T: Canvas> Graph3D<'a, 'c, 'o, T> {
+    pub fn new(canvas: &'c mut T, series: Vec<&'a (dyn Plottable3D + 'a)>, opts: &'o Graph3DOptions<'o>) -> Self {
+        let dimensions = Graph3D::<T>::dimensions_for(canvas, &series, opts);
+
+        Graph3D {
+            series: series,
+            canvas: canvas,
+            dimensions: dimensions,
+            opts: opts,
+        }
+    }
+
+    fn dimensions_for(canvas: &T, series: &[&(dyn Plottable3D + 'a)], opts: &Graph3DOptions) -> Dimensions3D {
+        let (width, height) = canvas.get_size();
+        let mut dimensions = Dimensions3D::new(width, height);
+        dimensions.border = opts.border;
+        dimensions.azimuth = opts.azimuth;
+        dimensions.elevation = opts.elevation;
+        dimensions.projection = opts.projection;
+
+        for s in series.iter() {
+            if let Some((min, max)) = s.extent() {
+                dimensions.adjust_for_extent(min, max);
+            }
+        }
+
+        dimensions
+    }
+
+    pub fn add_series(&mut self, series: &'a (dyn Plottable3D + 'a)) {
+        if let Some((min, max)) = series.extent() {
+            self.dimensions.adjust_for_extent(min, max);
+        }
+        self.series.push(series);
+    }
+
+    /// Fits the box around every series again, keeping the current view
+    pub fn fit_view_to_data(&mut self) {
+        let (azimuth, elevation) = (self.dimensions.azimuth, self.dimensions.elevation);
+        self.dimensions = Graph3D::<T>::dimensions_for(self.canvas, &self.series, self.opts);
+        self.dimensions.azimuth = azimuth;
+        self.dimensions.elevation = elevation;
+    }
+
+    fn labels(&self, loose: bool) -> (Label, Label, Label) {
+        let mut labeller = Labeller::in_base10();
+        labeller.loose = loose;
+
+        let (min, max) = (self.dimensions.min, self.dimensions.max);
+        let count = self.opts.tick_count;
+        (labeller.search(min.x, max.x, count), labeller.search(min.y, max.y, count), labeller.search(min.z, max.z, count))
+    }
+
+    // The edges of the box that the x, y and z ticks are drawn along, x and y
+    // are along the bottom nearest to the viewer and z is on the left
+    fn tick_edges(&self) -> [(GraphCoord3D, GraphCoord3D); 3] {
+        let dims = self.dimensions;
+        let (min, max) = (dims.min, dims.max);
+        let nearest = dims.nearest_corner();
+
+        let screen_x = |p: GraphCoord3D| dims.view_position(p).0;
+        let left = dims.corners().into_iter()
+            .filter(|c| c.z == min.z)
+            .fold(None, |acc: Option<GraphCoord3D>, c| match acc {
+                Some(a) if screen_x(a) <= screen_x(c) => Some(a),
+                _ => Some(c),
+            })
+            .unwrap_or(min);
+
+        [(GraphCoord3D::new(min.x, nearest.y, min.z), GraphCoord3D::new(max.x, nearest.y, min.z)),
+         (GraphCoord3D::new(nearest.x, min.y, min.z), GraphCoord3D::new(nearest.x, max.y, min.z)),
+         (left, GraphCoord3D::new(left.x, left.y, max.z))]
+    }
+
+    // The edges of the box that are behind everything else,
+    // which are the ones that don't touch the nearest corner
+    fn draw_box(&mut self) -> Result<(), T::Err> {
+        let dims = self.dimensions;
+        let nearest = dims.nearest_corner();
+        let corners = dims.corners();
+
+        self.canvas.set_color(Color(160, 160, 160));
+        for (n, &a) in corners.iter().enumerate() {
+            for &b in corners[n + 1..].iter() {
+                let shared = (a.x == b.x) as u8 + (a.y == b.y) as u8 + (a.z == b.z) as u8;
+                if shared != 2 || a == nearest || b == nearest {
+                    continue;
+                }
+
+                if let (Some((a, _)), Some((b, _))) = (dims.convert_to_pixel(a), dims.convert_to_pixel(b)) {
+                    self.canvas.draw_line(a, b)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn draw_ticks(&mut self, labels: (Label, Label, Label)) -> Result<(), T::Err> {
+        let dims = self.dimensions;
+        let centre = dims.convert_to_pixel(GraphCoord3D::new((dims.min.x + dims.max.x) / 2.0,
+                                                             (dims.min.y + dims.max.y) / 2.0,
+                                                             (dims.min.z + dims.max.z) / 2.0));
+        let centre = match centre {
+            Some((centre, _)) => centre,
+            None => return Ok(()),
+        };
+
+        let edges = self.tick_edges();
+        let axis_labels = [self.opts.x_label, self.opts.y_label, self.opts.z_label];
+
+        self.canvas.set_color(Color(0, 0, 0));
+        for (n, &label) in [labels.0, labels.1, labels.2].iter().enumerate() {
+            let (start, end) = edges[n];
+            let at = |t: f64| match n {
+                0 => GraphCoord3D::new(t, start.y, start.z),
+                1 => GraphCoord3D::new(start.x, t, start.z),
+                _ => GraphCoord3D::new(start.x, start.y, t),
+            };
+
+            // labels are moved away from the middle of the box so they don't
+            // overlap it, the z labels are only moved to the left
+            let middle = match dims.convert_to_pixel(GraphCoord3D::new((start.x + end.x) / 2.0, (start.y + end.y) / 2.0, (start.z + end.z) / 2.0)) {
+                Some((middle, _)) => middle,
+                None => continue,
+            };
+            let direction = if n == 2 {
+                Pixel::new(-1.0, 0.0)
+            } else {
+                let (dx, dy) = (middle.x - centre.x, middle.y - centre.y);
+                let length = (dx * dx + dy * dy).sqrt().max(1e-9);
+                Pixel::new(dx / length, dy / length)
+            };
+            let offset = |pix: Pixel, distance: f64| Pixel::new(pix.x + direction.x * distance, pix.y + direction.y * distance);
+
+            let mut t = label.min;
+            while label.step > 0.0 && t <= label.max + label.step * 1e-9 {
+                if let Some((pix, _)) = dims.convert_to_pixel(at(t)) {
+                    self.canvas.draw_line(pix, offset(pix, 5.0))?;
+                    self.canvas.write_num_centred(t, offset(pix, TICK_OFFSET))?;
+                }
+                t += label.step;
+            }
+
+            self.canvas.write_text_centred(axis_labels[n], offset(middle, LABEL_OFFSET))?;
+        }
+
+        Ok(())
+    }
+
+    // Projects every shape of every series and draws them
+    // starting with the one furthest away
+    fn draw_shapes(&mut self) -> Result<(), T::Err> {
+        let dims = self.dimensions;
+        let mut projected = Vec::new();
+
+        for s in self.series.iter() {
+            for shape in s.shapes() {
+                let points = shape.points().into_iter()
+                    .map(|p| dims.convert_to_pixel(p))
+                    .collect::<Option<Vec<_>>>();
+
+                if let Some(points) = points {
+                    let depth = points.iter().map(|&(_, d)| d).sum::<f64>() / (points.len() as f64);
+                    let pixels = points.into_iter().map(|(p, _)| p).collect::<Vec<_>>();
+                    projected.push((depth, shape, pixels));
+                }
+            }
+        }
+
+        projected.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+
+        for (_, shape, pixels) in projected {
+            match shape {
+                Shape3D::Polygon(_, color, outline) => {
+                    self.canvas.set_color(color);
+                    self.canvas.fill_polygon(&pixels)?;
+
+                    if let Some(outline) = outline {
+                        self.canvas.set_color(outline);
+                        for (n, &a) in pixels.iter().enumerate() {
+                            self.canvas.draw_line(a, pixels[(n + 1) % pixels.len()])?;
+                        }
+                    }
+                },
+                Shape3D::Line(_, _, color) => {
+                    self.canvas.set_color(color);
+                    self.canvas.draw_line(pixels[0], pixels[1])?;
+                },
+                Shape3D::Point(_, color) => {
+                    self.canvas.set_color(color);
+                    Point::draw_marker(PointStyle::Cross, pixels[0], self.canvas)?;
+                },
+            }
+        }
+
+        Ok(())
+    }
+
+    fn redraw(&mut self, prettify_bounds: bool) -> Result<(), T::Err> {
+        self.canvas.set_color(Color(255, 255, 255));
+        self.canvas.clear();
+
+        if self.dimensions.max.x < self.dimensions.min.x {
+            self.canvas.show();
+            return Ok(());
+        }
+
+        let labels = self.labels(prettify_bounds);
+        if prettify_bounds {
+            self.dimensions.min = GraphCoord3D::new(labels.0.min, labels.1.min, labels.2.min);
+            self.dimensions.max = GraphCoord3D::new(labels.0.max, labels.1.max, labels.2.max);
+        }
+
+        self.draw_box()?;
+        self.draw_shapes()?;
+        self.draw_ticks(labels)?;
+
+        self.canvas.show();
+        Ok(())
+    }
+
+    pub fn show(&mut self) -> Result<(), T::Err> {
+        self.redraw(true)
+    }
+
+    /// Turns the view by `azimuth` degrees around the z axis and
+    /// tilts it by `elevation` degrees
+    pub fn rotate(&mut self, azimuth: f64, elevation: f64) -> Result<(), T::Err> {
+        self.dimensions.rotate(azimuth, elevation);
+        self.redraw(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::mock_canvas::MockCanvas;
+    use grid::Grid;
+    use options::{Graph3DOptions, Projection};
+    use pixel::GraphCoord3D;
+    use plottable::graphs_3d::{SurfaceSeries, Scatter3DSeries};
+
+    #[test]
+    fn test_show() {
+        let mut fake_canvas = MockCanvas::new();
+        let opts = Graph3DOptions::default().projection(Projection::Perspective).z_label("height");
+
+        let xs = (0..10).map(|n| (n as f64) * 0.3).collect::<Vec<_>>();
+        let grid = Grid::from_fn(xs.clone(), xs, |x, y| x.sin() * y.cos());
+        let surface = SurfaceSeries::new(&grid);
+        let points = [GraphCoord3D::new(0.5, 0.5, 2.1)];
+        let scatter = Scatter3DSeries::new(&points);
+
+        {
+            let mut graph = Graph3D::new(&mut fake_canvas, vec!(&surface), &opts);
+            graph.add_series(&scatter);
+            assert_eq!(graph.show(), Ok(()));
+
+            // the box should have been made to look nice around everything
+            assert!(graph.dimensions.min.x <= 0.0 && graph.dimensions.max.x >= 2.7);
+            assert!(graph.dimensions.max.z >= 2.1);
+
+            let before = graph.dimensions;
+            assert_eq!(graph.rotate(45.0, 100.0), Ok(()));
+            assert_eq!(graph.dimensions.azimuth, -15.0);
+            assert_eq!(graph.dimensions.elevation, 90.0);
+            assert_eq!((graph.dimensions.min, graph.dimensions.max), (before.min, before.max));
+        }
+
+        assert_eq!(fake_canvas.shown, 2);
+        assert_eq!(fake_canvas.cleared, 2);
+    }
+}
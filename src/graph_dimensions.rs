@@ -3,6 +3,7 @@ use utils;
 use data_set::DataSet;
 use canvas::Canvas;
 use plottable::Axis;
+use options::PlotStyle;
 use std::f64;
 
 #[derive(Clone, Copy, PartialEq, Debug)]
@@ -42,6 +43,13 @@ impl GraphDimensions {
         dimensions
     }
     
+    /// Sets the borders to a fixed amount of pixels instead of a percentage
+    /// of the size, which leaves more room for the graph on small canvases
+    pub fn set_border_pixels(&mut self, horizontal: f64, vertical: f64) {
+        self.horizontal_border = if self.width > 0.0 { horizontal / self.width } else { 0.0 };
+        self.vertical_border = if self.height > 0.0 { vertical / self.height } else { 0.0 };
+    }
+
     // The width of the horizontal_border in pixels
     // instead of percent
     fn horizontal_border(&self) -> f64 {
@@ -69,6 +77,21 @@ impl GraphDimensions {
         self.max.y - self.min.y
     }
 
+    /// The bottom left and top right corner of the area inside the borders
+    /// in pixels, this is used by anything that lays itself out without
+    /// using the co-ordinates of the graph such as a `RadarChart`
+    pub fn plot_area(&self) -> (Pixel, Pixel) {
+        let bottom_left = Pixel::new(self.horizontal_border(), self.vertical_border());
+        let top_right = Pixel::new(self.width - self.horizontal_border(), self.height - self.vertical_border());
+
+        (bottom_left, top_right)
+    }
+
+    /// The width and height of a single pixel in graph co-ordinates
+    pub fn pixel_size(&self) -> (f64, f64) {
+        (self.x_range() / self.actual_width(), self.y_range() / self.actual_height())
+    }
+
     pub fn convert_to_pixel<G: Into<GraphCoord>>(&self, gp: G) -> Option<Pixel> {
         let gp = gp.into();
 
@@ -110,11 +133,30 @@ impl GraphDimensions {
 
 
     pub fn adjust_for(&mut self, ds: &DataSet) {
-        let max = utils::get_max_coord(&[self.max, ds.get_max_coord()]);
-        let min = utils::get_min_coord(&[self.min, ds.get_min_coord()]);
+        let (mut min, mut max) = (ds.get_min_extent(), ds.get_max_extent());
+
+        // bubbles are a fixed size in pixels, so leave enough room
+        // that the biggest bubble isn't cut off at the edge
+        if ds.options.plot_style == PlotStyle::Bubble {
+            let radius = ds.options.bubble_radius.1;
+            let pad = |range: f64, pixels: f64| {
+                let range = if range > 0.0 { range } else { 1.0 };
+                range * radius / (pixels - 2.0 * radius).max(1.0)
+            };
+
+            let pad_x = pad(max.x - min.x, self.actual_width());
+            let pad_y = pad(max.y - min.y, self.actual_height());
+            min = GraphCoord::new(min.x - pad_x, min.y - pad_y);
+            max = GraphCoord::new(max.x + pad_x, max.y + pad_y);
+        }
+
+        self.adjust_for_extent(min, max);
+    }
 
-        self.max = max;
-        self.min = min;
+    /// Grows the dimensions so that everything between `min` and `max` is on the grid
+    pub fn adjust_for_extent(&mut self, min: GraphCoord, max: GraphCoord) {
+        self.max = utils::get_max_coord(&[self.max, max]);
+        self.min = utils::get_min_coord(&[self.min, min]);
     }
 
     // Axis is a special case since the labelling algorithm will
@@ -134,7 +176,8 @@ impl GraphDimensions {
     pub fn off_grid<G: Into<GraphCoord>>(&self, g: G) -> bool {
         let GraphCoord { x, y } = g.into();
 
-        x > self.max.x || x < self.min.x || y > self.max.y || y < self.min.y
+        // written this way round so that NaN is always off the grid
+        !(x <= self.max.x && x >= self.min.x && y <= self.max.y && y >= self.min.y)
     }
 }
 
@@ -142,8 +185,8 @@ impl GraphDimensions {
 mod tests {
     use super::*;
     use pixel::{GraphCoord, Pixel};
-    use data_set::DataSet;
-    use options::DataSetOptions;
+    use data_set::{DataSet, ErrorBar};
+    use options::{DataSetOptions, PlotStyle};
 
     #[test]
     fn test_convert_to_pixels() {
@@ -169,6 +212,7 @@ mod tests {
 
         // convert_to_pixel should return None on out of bounds
         assert_eq!(dim.convert_to_pixel((20.0, -3.0)), None);
+        assert_eq!(dim.convert_to_pixel((f64::NAN, 1.0)), None);
 
         // this is meaningless, so we don't have to return anything useful
         // however it should not panic
@@ -208,4 +252,33 @@ mod tests {
         assert_eq!(dim.max, GraphCoord::new(20.0, 10.0));
         assert_eq!(dim.min, GraphCoord::new(-10.0, -15.0)); 
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_adjust_for_errors() {
+        let mut dim = GraphDimensions::new(600.0, 600.0);
+
+        // the ends of the error bars should be on the grid, not just the points
+        let default_ops = DataSetOptions::default();
+        let ds = DataSet::from_vecs(vec!(0.0, 10.0), vec!(5.0, 5.0), &default_ops).unwrap()
+            .y_errors(vec!(ErrorBar::symmetric(1.0), ErrorBar::new(2.0, 3.0))).unwrap()
+            .x_errors(vec!(ErrorBar::new(0.5, 0.0), ErrorBar::symmetric(0.0))).unwrap();
+        dim.adjust_for(&ds);
+
+        assert_eq!(dim.max, GraphCoord::new(10.0, 8.0));
+        assert_eq!(dim.min, GraphCoord::new(-0.5, 3.0));
+    }
+
+    #[test]
+    fn test_adjust_for_bubbles() {
+        let mut dim = GraphDimensions::new(600.0, 600.0);
+
+        // 480 pixels wide once the borders are removed, so a 40 pixel bubble
+        // needs 40 / 400 of the range added to each side
+        let bubble_opts = DataSetOptions::default().plot_style(PlotStyle::Bubble).bubble_radius(5.0, 40.0);
+        let ds = DataSet::from_vecs(vec!(0.0, 10.0), vec!(0.0, 20.0), &bubble_opts).unwrap();
+        dim.adjust_for(&ds);
+
+        assert_eq!(dim.max, GraphCoord::new(11.0, 22.0));
+        assert_eq!(dim.min, GraphCoord::new(-1.0, -2.0));
+    }
+}
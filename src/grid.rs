@@ -0,0 +1,61 @@
+use pixel::GraphCoord;
+use std::f64;
+
+/// `Grid` holds a scalar value for each point on a rectangular grid
+/// `z[j][i]` is the value at `(x[i], y[j])`
+#[derive(Clone, PartialEq, Debug)]
+pub struct Grid {
+    pub x: Vec<f64>,
+    pub y: Vec<f64>,
+    pub z: Vec<Vec<f64>>,
+}
+
+impl Grid {
+    /// Creates a new `Grid`, returns `None` if the number of rows in `z`
+    /// doesn't match `y` or any row doesn't match `x`
+    pub fn new(x: Vec<f64>, y: Vec<f64>, z: Vec<Vec<f64>>) -> Option<Self> {
+        if z.len() != y.len() || z.iter().any(|row| row.len() != x.len()) {
+            return None;
+        }
+
+        Some(Grid { x: x, y: y, z: z })
+    }
+
+    /// Takes the x and y co-ordinates of the grid and uses the given
+    /// function f to calculate the value at each point
+    pub fn from_fn<F>(x: Vec<f64>, y: Vec<f64>, f: F) -> Self
+        where F: Fn(f64, f64) -> f64 {
+
+        let z = y.iter()
+            .map(|&y| x.iter().map(|&x| f(x, y)).collect::<Vec<f64>>())
+            .collect::<Vec<_>>();
+
+        Grid::new(x, y, z).expect("rows are built from x and y")
+    }
+
+    pub fn get_max_coord(&self) -> GraphCoord {
+        let x = self.x.iter().fold(f64::MIN, |acc, &x| acc.max(x));
+        let y = self.y.iter().fold(f64::MIN, |acc, &y| acc.max(y));
+        GraphCoord::new(x, y)
+    }
+
+    pub fn get_min_coord(&self) -> GraphCoord {
+        let x = self.x.iter().fold(f64::MAX, |acc, &x| acc.min(x));
+        let y = self.y.iter().fold(f64::MAX, |acc, &y| acc.min(y));
+        GraphCoord::new(x, y)
+    }
+
+    /// The largest finite value held in the grid
+    pub fn max_value(&self) -> f64 {
+        self.values().fold(f64::MIN, |acc, z| acc.max(z))
+    }
+
+    /// The smallest finite value held in the grid
+    pub fn min_value(&self) -> f64 {
+        self.values().fold(f64::MAX, |acc, z| acc.min(z))
+    }
+
+    fn values<'a>(&'a self) -> Box<dyn Iterator<Item=f64> + 'a> {
+        Box::new(self.z.iter().flat_map(|row| row.iter().cloned()).filter(|z| z.is_finite()))
+    }
+}
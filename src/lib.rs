@@ -6,6 +6,11 @@
 //! the data for a graph as well as any options such as what colour to draw the graph
 //! The `Graph2D` is the main struct in this crate, it holds several DataSets
 //! and an axis to plot a graph.
+//! The `PolarGraph` is similar to `Graph2D` however it reads each point
+//! of a `DataSet` as an angle and a distance from the centre
+//! The `Graph3D` draws surfaces, wireframes, lines and points in 3D
+//! and can be viewed from any angle
+//! The `TernaryGraph` plots compositions of three components inside a triangle
 
 extern crate sdl2;
 extern crate rand;
@@ -19,9 +24,30 @@ mod data_set;
 pub mod plottable;
 mod graph_dimensions;
 mod labeller;
+pub mod colormap;
+mod grid;
+mod marching_squares;
+mod polar_graph;
+mod polar_dimensions;
+mod graph_3d;
+mod dimensions_3d;
+mod ternary_graph;
+mod ternary_dimensions;
+mod sampler;
+mod stats;
+mod sparkline;
 
-pub use pixel::Pixel;
+pub use pixel::{Pixel, GraphCoord3D};
 pub use canvas::Canvas;
-pub use data_set::DataSet;
+pub use data_set::{DataSet, ErrorBar};
 pub use graph_dimensions::GraphDimensions;
 pub use graph_2d::Graph2D;
+pub use polar_graph::PolarGraph;
+pub use polar_dimensions::PolarDimensions;
+pub use graph_3d::Graph3D;
+pub use dimensions_3d::Dimensions3D;
+pub use ternary_graph::{TernaryGraph, TernarySeries, TernaryStyle};
+pub use ternary_dimensions::TernaryDimensions;
+pub use grid::Grid;
+pub use colormap::Colormap;
+pub use sparkline::Sparkline;
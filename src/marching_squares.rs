@@ -0,0 +1,179 @@
+//! Implementation of the marching squares algorithm for finding iso-lines
+//! in a `Grid` as well as the regions between two levels, which are found by
+//! splitting each cell into two triangles and clipping them to the band
+
+use grid::Grid;
+use pixel::GraphCoord;
+use plottable::primitives::Line;
+
+/// Finds every line segment where the grid crosses `level`
+pub fn iso_lines(grid: &Grid, level: f64) -> Vec<Line> {
+    let mut lines = Vec::new();
+
+    for j in 0..grid.y.len().saturating_sub(1) {
+        for i in 0..grid.x.len().saturating_sub(1) {
+            let corners = cell_corners(grid, i, j);
+            lines.extend(cell_lines(&corners, level));
+        }
+    }
+
+    lines
+}
+
+/// Finds the line segments where a single cell crosses `level`, the corners
+/// should be given anticlockwise starting at the bottom left
+pub fn cell_lines(corners: &[(GraphCoord, f64); 4], level: f64) -> Vec<Line> {
+    if corners.iter().any(|&(_, z)| !z.is_finite()) {
+        return Vec::new();
+    }
+
+    let case = corners.iter().enumerate().fold(0, |acc, (n, &(_, z))| {
+        if z >= level { acc | (1 << n) } else { acc }
+    });
+
+    // edge n joins corner n to corner n + 1
+    let edge = |n: usize| {
+        let (a, za) = corners[n];
+        let (b, zb) = corners[(n + 1) % 4];
+        interpolate(a, za, b, zb, level)
+    };
+
+    match case {
+        0 | 15 => vec!(),
+        1 | 14 => vec!(Line(edge(3), edge(0))),
+        2 | 13 => vec!(Line(edge(0), edge(1))),
+        3 | 12 => vec!(Line(edge(3), edge(1))),
+        4 | 11 => vec!(Line(edge(1), edge(2))),
+        6 | 9 => vec!(Line(edge(0), edge(2))),
+        7 | 8 => vec!(Line(edge(2), edge(3))),
+        // saddle points, use the average of the corners to decide
+        // which pair of corners are connected
+        5 | 10 => {
+            let centre = corners.iter().fold(0.0, |acc, &(_, z)| acc + z) / 4.0;
+            let centre_high = centre >= level;
+            if (case == 5) == centre_high {
+                vec!(Line(edge(0), edge(1)), Line(edge(2), edge(3)))
+            } else {
+                vec!(Line(edge(3), edge(0)), Line(edge(1), edge(2)))
+            }
+        },
+        _ => unreachable!(),
+    }
+}
+
+/// Finds the polygons covering every part of the grid with a value
+/// between `low` and `high`
+pub fn band_polygons(grid: &Grid, low: f64, high: f64) -> Vec<Vec<GraphCoord>> {
+    let mut polygons = Vec::new();
+
+    for j in 0..grid.y.len().saturating_sub(1) {
+        for i in 0..grid.x.len().saturating_sub(1) {
+            let c = cell_corners(grid, i, j);
+            if c.iter().any(|&(_, z)| !z.is_finite()) {
+                continue;
+            }
+
+            for triangle in &[[c[0], c[1], c[2]], [c[0], c[2], c[3]]] {
+                let above = clip_to_level(triangle, low, true);
+                let band = clip_to_level(&above, high, false);
+                if band.len() >= 3 {
+                    polygons.push(band.into_iter().map(|(p, _)| p).collect());
+                }
+            }
+        }
+    }
+
+    polygons
+}
+
+fn cell_corners(grid: &Grid, i: usize, j: usize) -> [(GraphCoord, f64); 4] {
+    [
+        (GraphCoord::new(grid.x[i], grid.y[j]), grid.z[j][i]),
+        (GraphCoord::new(grid.x[i + 1], grid.y[j]), grid.z[j][i + 1]),
+        (GraphCoord::new(grid.x[i + 1], grid.y[j + 1]), grid.z[j + 1][i + 1]),
+        (GraphCoord::new(grid.x[i], grid.y[j + 1]), grid.z[j + 1][i]),
+    ]
+}
+
+fn interpolate(a: GraphCoord, za: f64, b: GraphCoord, zb: f64, level: f64) -> GraphCoord {
+    let t = if zb == za { 0.5 } else { (level - za) / (zb - za) };
+    GraphCoord::new(a.x + t * (b.x - a.x), a.y + t * (b.y - a.y))
+}
+
+// Sutherland-Hodgman clipping, except the clip edge is where the
+// interpolated value crosses `level` instead of a straight line
+fn clip_to_level(points: &[(GraphCoord, f64)], level: f64, keep_above: bool) -> Vec<(GraphCoord, f64)> {
+    let mut output = Vec::new();
+    if points.is_empty() {
+        return output;
+    }
+
+    let inside = |z: f64| if keep_above { z >= level } else { z <= level };
+
+    let mut prev = points[points.len() - 1];
+    for &curr in points.iter() {
+        let crossing = (interpolate(prev.0, prev.1, curr.0, curr.1, level), level);
+        match (inside(prev.1), inside(curr.1)) {
+            (true, true) => output.push(curr),
+            (true, false) => output.push(crossing),
+            (false, true) => {
+                output.push(crossing);
+                output.push(curr);
+            },
+            (false, false) => {},
+        }
+        prev = curr;
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grid::Grid;
+
+    #[test]
+    fn test_iso_lines() {
+        // a single cell with one corner above the level should give one line
+        let grid = Grid::new(vec!(0.0, 1.0), vec!(0.0, 1.0), vec!(vec!(1.0, 0.0), vec!(0.0, 0.0))).unwrap();
+        let lines = iso_lines(&grid, 0.5);
+
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0], Line(GraphCoord::new(0.0, 0.5), GraphCoord::new(0.5, 0.0)));
+
+        // a level outside the range of the grid shouldn't give anything
+        assert_eq!(iso_lines(&grid, 2.0), vec!());
+    }
+
+    #[test]
+    fn test_circle_is_closed() {
+        let xs = (0..21).map(|i| (i as f64) / 10.0 - 1.0).collect::<Vec<_>>();
+        let grid = Grid::from_fn(xs.clone(), xs, |x, y| x * x + y * y);
+        let lines = iso_lines(&grid, 0.55);
+
+        // every end of a segment on a closed curve should be shared with another segment
+        let shared = |p: GraphCoord| {
+            lines.iter().filter(|l| (l.0.x - p.x).abs() < 1e-9 && (l.0.y - p.y).abs() < 1e-9 ||
+                                    (l.1.x - p.x).abs() < 1e-9 && (l.1.y - p.y).abs() < 1e-9).count()
+        };
+        assert!(!lines.is_empty());
+        assert!(lines.iter().all(|l| shared(l.0) == 2 && shared(l.1) == 2));
+    }
+
+    #[test]
+    fn test_band_polygons() {
+        let grid = Grid::from_fn(vec!(0.0, 1.0), vec!(0.0, 1.0), |x, _| x);
+
+        // the band 0.25 to 0.75 should cover exactly half the cell
+        let area = band_polygons(&grid, 0.25, 0.75).iter().fold(0.0, |acc, poly| {
+            let n = poly.len();
+            acc + (0..n).fold(0.0, |a, i| {
+                let (p, q) = (poly[i], poly[(i + 1) % n]);
+                a + (p.x * q.y - q.x * p.y)
+            }).abs() / 2.0
+        });
+
+        assert!((area - 0.5).abs() < 1e-9);
+    }
+}
@@ -1,12 +1,74 @@
 use pixel::Color;
 
 /// `PlotStyle` determines whether a `Graph2D` should be plotted
-/// as a Bar, Line or Scatter graph
+/// as a Bar, Line, Scatter, Area, Step, Stem or Bubble graph
 #[derive(Clone, Copy, PartialEq)]
 pub enum PlotStyle {
     Bar,
     Line,
     Scatter,  
+    /// A line graph with the region between the line and the baseline filled
+    Area,
+    /// A line graph which only uses horizontal and vertical lines
+    Step(StepMode),
+    /// A vertical line from the baseline to each point, topped with a marker
+    Stem,
+    /// A filled circle at each point with an area set by the size of the point
+    Bubble,
+}
+
+/// `SizeScale` determines how the size of a point in a bubble graph
+/// is turned into the radius of the bubble
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SizeScale {
+    /// The radius grows linearly with the size
+    Linear,
+    /// The radius grows from zero with the square root of the size
+    /// so that the area of the bubble is proportional to the size,
+    /// only bubbles too small to see are given the smallest radius
+    Sqrt,
+}
+
+/// `StepMode` determines where the vertical line of a step graph
+/// is placed between two points
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum StepMode {
+    /// The step happens at the first x value, so each y value
+    /// is held over the interval to its left
+    Pre,
+    /// The step happens at the second x value, so each y value
+    /// is held over the interval to its right
+    Post,
+    /// The step happens halfway between the two x values
+    Mid,
+}
+
+/// `Orientation` specifies which way a bar graph should be drawn
+/// For a vertical bar graph the bars grow along the y axis
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Orientation {
+    Vertical,
+    Horizontal,
+}
+
+/// `BarLayout` determines how a `BarChart` arranges bars from
+/// several `DataSet`s that share an x value
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BarLayout {
+    /// Bars are placed side by side
+    Grouped,
+    /// Bars are placed on top of each other
+    Stacked,
+    /// Bars are placed on top of each other and scaled so each stack is 100 high
+    Normalised,
+}
+
+/// `ErrorBarStyle` specifies whether error bars should have
+/// a cap drawn across each end
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ErrorBarStyle {
+    Plain,
+    Capped,
 }
 
 /// `PointStyle` specifies whether markers should be drawn
@@ -15,7 +77,7 @@ pub enum PlotStyle {
 pub enum PointStyle {
     Nothing,
     Cross,
-    // could have circle, etc.
+    Circle,
 }
 
 /// `AxisOptions` contains options that are specific to the graph
@@ -41,6 +103,10 @@ pub struct AxisOptions<'a> {
     /// it will be placed halfway between the maximum
     /// value and the origin
     pub label: &'a str,
+
+    /// If true nothing is drawn along this axis, not even its label,
+    /// so the other axis can be drawn on its own
+    pub hidden: bool,
 }
 
 impl <'a> AxisOptions<'a> {
@@ -74,6 +140,11 @@ impl <'a> AxisOptions<'a> {
         self.number_offset = number_offset;
         self
     }
+
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
 }
 
 impl <'a> Default for AxisOptions<'a> {
@@ -84,6 +155,7 @@ impl <'a> Default for AxisOptions<'a> {
             number_offset: 0.03,
             label_offset: 0.075,
             label: "",
+            hidden: false,
         }
     }
 }
@@ -96,6 +168,40 @@ pub struct DataSetOptions<'a> {
     pub random_color: bool,
     pub colors: Option<&'a [Color]>,
     pub name: &'a str,
+
+    /// The y value that area and bar graphs are filled down to
+    /// and that the lines of a stem graph start from
+    pub baseline: f64,
+
+    /// The colour of the baseline drawn by a stem graph
+    pub baseline_color: Color,
+
+    /// The width of each bar in a bar graph
+    pub bar_width: f64,
+
+    /// Whether the bars in a bar graph should be vertical or horizontal
+    /// for a horizontal bar graph the x value of each point is its position
+    /// on the y axis and the y value is how far the bar extends along the x axis
+    pub orientation: Orientation,
+
+    /// The colour used to fill an area graph, if this is `None`
+    /// the same colour as the line is used
+    pub fill_color: Option<Color>,
+
+    /// The opacity of the fill for an area graph, 255 is fully opaque
+    pub fill_alpha: u8,
+
+    /// Whether any error bars should be drawn with caps
+    pub error_style: ErrorBarStyle,
+
+    /// The width of the caps on the error bars in pixels
+    pub error_cap_width: f64,
+
+    /// How the size of each point is turned into a radius in a bubble graph
+    pub size_scale: SizeScale,
+
+    /// The smallest and largest radius of a bubble in pixels
+    pub bubble_radius: (f64, f64),
 }
 
 impl <'a> DataSetOptions<'a> {
@@ -134,6 +240,58 @@ impl <'a> DataSetOptions<'a> {
         self.name = name;
         self
     }
+
+    pub fn baseline(mut self, baseline: f64) -> Self {
+        self.baseline = baseline;
+        self
+    }
+
+    pub fn baseline_color(mut self, baseline_color: Color) -> Self {
+        self.baseline_color = baseline_color;
+        self
+    }
+
+    pub fn bar_width(mut self, bar_width: f64) -> Self {
+        self.bar_width = bar_width;
+        self
+    }
+
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    pub fn fill_color<C>(mut self, fill_color: C) -> Self
+        where C: Into<Option<Color>> {
+
+        self.fill_color = fill_color.into();
+        self
+    }
+
+    pub fn fill_alpha(mut self, fill_alpha: u8) -> Self {
+        self.fill_alpha = fill_alpha;
+        self
+    }
+
+    pub fn error_style(mut self, error_style: ErrorBarStyle) -> Self {
+        self.error_style = error_style;
+        self
+    }
+
+    pub fn error_cap_width(mut self, error_cap_width: f64) -> Self {
+        self.error_cap_width = error_cap_width;
+        self
+    }
+
+    pub fn size_scale(mut self, size_scale: SizeScale) -> Self {
+        self.size_scale = size_scale;
+        self
+    }
+
+    pub fn bubble_radius(mut self, min: f64, max: f64) -> Self {
+        self.bubble_radius = (min, max);
+        self
+    }
 }
 
 impl <'a> Default for DataSetOptions<'a> {
@@ -145,6 +303,362 @@ impl <'a> Default for DataSetOptions<'a> {
             random_color: false,
             colors: None,
             name: "",
+            baseline: 0.0,
+            baseline_color: Color(128, 128, 128),
+            bar_width: 1.0,
+            orientation: Orientation::Vertical,
+            fill_color: None,
+            fill_alpha: 255,
+            error_style: ErrorBarStyle::Capped,
+            error_cap_width: 6.0,
+            size_scale: SizeScale::Sqrt,
+            bubble_radius: (3.0, 30.0),
         }
     }
-}
\ No newline at end of file
+}
+
+/// `FinancialOptions` contains the options for a `CandlestickSeries`
+/// or an `OhlcSeries`
+#[derive(Clone, PartialEq)]
+pub struct FinancialOptions<'a> {
+    /// The colour of a record that closed higher than it opened
+    pub up_color: Color,
+
+    /// The colour of a record that closed lower than it opened
+    pub down_color: Color,
+
+    /// The width of each candle as a fraction of the smallest
+    /// gap between two records
+    pub width: f64,
+
+    /// If true, records are placed one after another instead of at their x value
+    /// so that any gaps such as non-trading days are skipped, the x axis of
+    /// a `Graph2D` then shows the x value of every few records
+    pub category_axis: bool,
+
+    /// The fraction of the height of the graph used to show the volume
+    /// of each record under the prices, 0 means the volume isn't shown
+    /// The y axis of a `Graph2D` then labels the prices and the volume on their own scales
+    pub volume_panel: f64,
+
+    pub name: &'a str,
+}
+
+impl <'a> FinancialOptions<'a> {
+    pub fn new() -> FinancialOptions<'a> {
+        FinancialOptions::default()
+    }
+
+    pub fn up_color(mut self, up_color: Color) -> Self {
+        self.up_color = up_color;
+        self
+    }
+
+    pub fn down_color(mut self, down_color: Color) -> Self {
+        self.down_color = down_color;
+        self
+    }
+
+    pub fn width(mut self, width: f64) -> Self {
+        self.width = width;
+        self
+    }
+
+    pub fn category_axis(mut self, category_axis: bool) -> Self {
+        self.category_axis = category_axis;
+        self
+    }
+
+    pub fn volume_panel(mut self, volume_panel: f64) -> Self {
+        self.volume_panel = volume_panel;
+        self
+    }
+
+    pub fn name(mut self, name: &'a str) -> Self {
+        self.name = name;
+        self
+    }
+}
+
+impl <'a> Default for FinancialOptions<'a> {
+    fn default() -> FinancialOptions<'a> {
+        FinancialOptions {
+            up_color: Color(0, 160, 0),
+            down_color: Color(200, 0, 0),
+            width: 0.7,
+            category_axis: false,
+            volume_panel: 0.0,
+            name: "",
+        }
+    }
+}
+
+/// `AngleUnit` determines how the angles on a polar graph are labelled
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AngleUnit {
+    Degrees,
+    Radians,
+}
+
+/// `ZeroDirection` is the direction that an angle of zero points
+/// in on a polar graph
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ZeroDirection {
+    East,
+    North,
+    West,
+    South,
+}
+
+/// `Winding` is the direction that angles increase in on a polar graph
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Winding {
+    Anticlockwise,
+    Clockwise,
+}
+
+/// `PolarOptions` contains the options for the grid of a `PolarGraph`
+#[derive(Clone, PartialEq)]
+pub struct PolarOptions<'a> {
+    /// The amount of rings the `Labeller` should aim for
+    pub ring_count: f64,
+
+    /// The amount of spokes drawn from the centre of the graph
+    pub spoke_count: i32,
+
+    pub angle_unit: AngleUnit,
+    pub zero_direction: ZeroDirection,
+    pub winding: Winding,
+
+    /// The percent of the width or height of the canvas, whichever is smaller,
+    /// that is left empty around the graph
+    pub border: f64,
+
+    /// A label that will be written above the graph
+    pub label: &'a str,
+}
+
+impl <'a> PolarOptions<'a> {
+    pub fn new() -> PolarOptions<'a> {
+        PolarOptions::default()
+    }
+
+    pub fn ring_count(mut self, ring_count: f64) -> Self {
+        self.ring_count = ring_count;
+        self
+    }
+
+    pub fn spoke_count(mut self, spoke_count: i32) -> Self {
+        self.spoke_count = spoke_count;
+        self
+    }
+
+    pub fn angle_unit(mut self, angle_unit: AngleUnit) -> Self {
+        self.angle_unit = angle_unit;
+        self
+    }
+
+    pub fn zero_direction(mut self, zero_direction: ZeroDirection) -> Self {
+        self.zero_direction = zero_direction;
+        self
+    }
+
+    pub fn winding(mut self, winding: Winding) -> Self {
+        self.winding = winding;
+        self
+    }
+
+    pub fn border(mut self, border: f64) -> Self {
+        self.border = border;
+        self
+    }
+
+    pub fn label(mut self, label: &'a str) -> Self {
+        self.label = label;
+        self
+    }
+}
+
+impl <'a> Default for PolarOptions<'a> {
+    fn default() -> PolarOptions<'a> {
+        PolarOptions {
+            ring_count: 5.0,
+            spoke_count: 8,
+            angle_unit: AngleUnit::Degrees,
+            zero_direction: ZeroDirection::East,
+            winding: Winding::Anticlockwise,
+            border: 0.1,
+            label: "",
+        }
+    }
+}
+
+/// `Projection` is how the points of a `Graph3D` are flattened onto the canvas
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Projection {
+    /// Parallel lines stay parallel, no matter how far away they are
+    Orthographic,
+    /// Anything further away is drawn smaller
+    Perspective,
+}
+
+/// `Graph3DOptions` contains the options for the view and axis of a `Graph3D`
+#[derive(Clone, PartialEq)]
+pub struct Graph3DOptions<'a> {
+    pub projection: Projection,
+
+    /// The angle in degrees that the graph is turned around the z axis
+    pub azimuth: f64,
+    /// The angle in degrees that the graph is viewed from above the x-y plane
+    pub elevation: f64,
+
+    /// The amount of ticks the `Labeller` should aim for on each axis
+    pub tick_count: i32,
+
+    /// The percent of the width or height of the canvas, whichever is smaller,
+    /// that is left empty around the graph
+    pub border: f64,
+
+    pub x_label: &'a str,
+    pub y_label: &'a str,
+    pub z_label: &'a str,
+}
+
+impl <'a> Graph3DOptions<'a> {
+    pub fn new() -> Graph3DOptions<'a> {
+        Graph3DOptions::default()
+    }
+
+    pub fn projection(mut self, projection: Projection) -> Self {
+        self.projection = projection;
+        self
+    }
+
+    pub fn azimuth(mut self, azimuth: f64) -> Self {
+        self.azimuth = azimuth;
+        self
+    }
+
+    pub fn elevation(mut self, elevation: f64) -> Self {
+        self.elevation = elevation;
+        self
+    }
+
+    pub fn tick_count(mut self, tick_count: i32) -> Self {
+        self.tick_count = tick_count;
+        self
+    }
+
+    pub fn border(mut self, border: f64) -> Self {
+        self.border = border;
+        self
+    }
+
+    pub fn x_label(mut self, x_label: &'a str) -> Self {
+        self.x_label = x_label;
+        self
+    }
+
+    pub fn y_label(mut self, y_label: &'a str) -> Self {
+        self.y_label = y_label;
+        self
+    }
+
+    pub fn z_label(mut self, z_label: &'a str) -> Self {
+        self.z_label = z_label;
+        self
+    }
+}
+
+impl <'a> Default for Graph3DOptions<'a> {
+    fn default() -> Graph3DOptions<'a> {
+        Graph3DOptions {
+            projection: Projection::Orthographic,
+            azimuth: -60.0,
+            elevation: 30.0,
+            tick_count: 5,
+            border: 0.1,
+            x_label: "",
+            y_label: "",
+            z_label: "",
+        }
+    }
+}
+
+/// `TernaryOptions` contains the options for the grid of a `TernaryGraph`
+#[derive(Clone, PartialEq)]
+pub struct TernaryOptions<'a> {
+    /// The amount of gridlines the `Labeller` should aim for along each edge
+    pub tick_count: i32,
+
+    /// The amount that the three components of every point add up to,
+    /// such as 1 for fractions or 100 for percentages
+    pub total: f64,
+
+    /// The percent of the width or height of the canvas, whichever is smaller,
+    /// that is left empty around the graph
+    pub border: f64,
+
+    /// The names of the components at the top, bottom left and bottom right corners
+    pub a_label: &'a str,
+    pub b_label: &'a str,
+    pub c_label: &'a str,
+
+    /// A label that will be written above the graph
+    pub label: &'a str,
+}
+
+impl <'a> TernaryOptions<'a> {
+    pub fn new() -> TernaryOptions<'a> {
+        TernaryOptions::default()
+    }
+
+    pub fn tick_count(mut self, tick_count: i32) -> Self {
+        self.tick_count = tick_count;
+        self
+    }
+
+    pub fn total(mut self, total: f64) -> Self {
+        self.total = total;
+        self
+    }
+
+    pub fn border(mut self, border: f64) -> Self {
+        self.border = border;
+        self
+    }
+
+    pub fn a_label(mut self, a_label: &'a str) -> Self {
+        self.a_label = a_label;
+        self
+    }
+
+    pub fn b_label(mut self, b_label: &'a str) -> Self {
+        self.b_label = b_label;
+        self
+    }
+
+    pub fn c_label(mut self, c_label: &'a str) -> Self {
+        self.c_label = c_label;
+        self
+    }
+
+    pub fn label(mut self, label: &'a str) -> Self {
+        self.label = label;
+        self
+    }
+}
+
+impl <'a> Default for TernaryOptions<'a> {
+    fn default() -> TernaryOptions<'a> {
+        TernaryOptions {
+            tick_count: 5,
+            total: 1.0,
+            border: 0.15,
+            a_label: "",
+            b_label: "",
+            c_label: "",
+            label: "",
+        }
+    }
+}
@@ -28,6 +28,27 @@ impl Into<Pixel> for (f64, f64) {
     } 
 }
 
+/// A GraphCoord3D represents an (x, y, z) point on a `Graph3D`
+/// which is projected onto a Pixel by `Dimensions3D`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GraphCoord3D {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl GraphCoord3D {
+    pub fn new(x: f64, y: f64, z: f64) -> GraphCoord3D {
+        GraphCoord3D {x: x, y: y, z: z}
+    }
+}
+
+impl Into<GraphCoord3D> for (f64, f64, f64) {
+    fn into(self) -> GraphCoord3D {
+        GraphCoord3D::new(self.0, self.1, self.2)
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub struct Color(pub u8, pub u8, pub u8);
 
@@ -5,6 +5,14 @@ use plottable::Plottable;
 use labeller::{Labeller, Label};
 use pixel::{Color, Pixel};
 
+/// The label of a single category on an axis, for anything placed one after
+/// another rather than at its value, such as the rows of a `TimelineSeries`
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum Category<'a> {
+    Name(&'a str),
+    Value(f64),
+}
+
 #[derive(Copy, Clone, PartialEq)]
 pub struct Axis<'a> {
     x_opts: &'a AxisOptions<'a>,
@@ -12,6 +20,9 @@ pub struct Axis<'a> {
 
     pub x_label: Label,
     pub y_label: Label,
+
+    x_categories: &'a [(f64, Category<'a>)],
+    y_categories: &'a [(f64, Category<'a>)],
 }
 
 impl<'a> Axis<'a> {
@@ -21,9 +32,19 @@ impl<'a> Axis<'a> {
             y_opts: y_opts,
             x_label: x_label,
             y_label: y_label,
+            x_categories: &[],
+            y_categories: &[],
         }
     }
 
+    /// Labels each axis with its categories in place of the numbers
+    /// from its `Label`, an axis without any categories keeps its numbers
+    pub fn categories(mut self, x_categories: &'a [(f64, Category<'a>)], y_categories: &'a [(f64, Category<'a>)]) -> Self {
+        self.x_categories = x_categories;
+        self.y_categories = y_categories;
+        self
+    }
+
     pub fn from_dimensions(dimensions: &GraphDimensions,
         x_opts: &'a AxisOptions<'a>, y_opts: &'a AxisOptions<'a>) -> Axis<'a> {
 
@@ -51,43 +72,65 @@ impl<'a> Axis<'a> {
         canvas.draw_line(top_left, top_right)?;
         canvas.draw_line(bottom_right, top_right)?;
 
-        let mut x = bounds.min.x;
-        while x <= bounds.max.x {
-            let pix = bounds.convert_to_pixel((x, bounds.min.y))
-                .expect("axis should always be on grid");
-            
-            let tick_size = bounds.width * self.x_opts.tick_size;
-            canvas.draw_line(pix, Pixel::new(pix.x, pix.y - tick_size))?;
+        if !self.x_opts.hidden {
+            if self.x_categories.is_empty() {
+                let mut x = bounds.min.x;
+                while x <= bounds.max.x {
+                    self.draw_x_tick(x, Category::Value(x), bounds, canvas)?;
+                    x += self.x_label.step;
+                }
+            } else {
+                for &(x, category) in self.x_categories.iter().filter(|c| c.0 >= bounds.min.x && c.0 <= bounds.max.x) {
+                    self.draw_x_tick(x, category, bounds, canvas)?;
+                }
+            }
+        }
 
-            let number_offset = bounds.width * self.x_opts.number_offset;
-            canvas.write_num_centred(x, (pix.x, pix.y - number_offset))?;
+        if !self.y_opts.hidden {
+            if self.y_categories.is_empty() {
+                let mut y = bounds.min.y;
+                while y <= bounds.max.y {
+                    self.draw_y_tick(y, Category::Value(y), bounds, canvas)?;
+                    y += self.y_label.step;
+                }
+            } else {
+                for &(y, category) in self.y_categories.iter().filter(|c| c.0 >= bounds.min.y && c.0 <= bounds.max.y) {
+                    self.draw_y_tick(y, category, bounds, canvas)?;
+                }
+            }
+        }
 
-            let top = bounds.convert_to_pixel((x, bounds.max.y))
-                .expect("axis should always be on grid");
-            canvas.draw_line(pix, top)?;
+        Ok(())
+    }
 
-            x += self.x_label.step;
-        }
+    fn draw_x_tick<C: Canvas>(&self, x: f64, category: Category, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let pix = bounds.convert_to_pixel((x, bounds.min.y))
+            .expect("axis should always be on grid");
+        
+        let tick_size = bounds.width * self.x_opts.tick_size;
+        canvas.draw_line(pix, Pixel::new(pix.x, pix.y - tick_size))?;
 
-        let mut y = bounds.min.y;
-        while y <= bounds.max.y {
-            let pix = bounds.convert_to_pixel((bounds.min.x, y))
-                .expect("axis should always be on grid");
-            
-            let tick_size = bounds.height * self.y_opts.tick_size;
-            canvas.draw_line((pix.x, pix.y), (pix.x - tick_size, pix.y))?;
+        let number_offset = bounds.width * self.x_opts.number_offset;
+        write_category(category, Pixel::new(pix.x, pix.y - number_offset), canvas)?;
 
-            let number_offset = bounds.height * self.y_opts.number_offset;
-            canvas.write_num_centred(y, (pix.x - number_offset, pix.y))?;
+        let top = bounds.convert_to_pixel((x, bounds.max.y))
+            .expect("axis should always be on grid");
+        canvas.draw_line(pix, top)
+    }
 
-            let right = bounds.convert_to_pixel((bounds.max.x, y))
-                .expect("axis should always be on grid");
-            canvas.draw_line(pix, right)?;
+    fn draw_y_tick<C: Canvas>(&self, y: f64, category: Category, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let pix = bounds.convert_to_pixel((bounds.min.x, y))
+            .expect("axis should always be on grid");
+        
+        let tick_size = bounds.height * self.y_opts.tick_size;
+        canvas.draw_line((pix.x, pix.y), (pix.x - tick_size, pix.y))?;
 
-            y += self.y_label.step;
-        }
+        let number_offset = bounds.height * self.y_opts.number_offset;
+        write_category(category, Pixel::new(pix.x - number_offset, pix.y), canvas)?;
 
-        Ok(())
+        let right = bounds.convert_to_pixel((bounds.max.x, y))
+            .expect("axis should always be on grid");
+        canvas.draw_line(pix, right)
     }
 
     fn write_label<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
@@ -99,12 +142,25 @@ impl<'a> Axis<'a> {
         let x_offset = bounds.width * self.x_opts.label_offset;
         let y_offset = bounds.height * self.y_opts.label_offset;
 
-        canvas.write_text_centred(self.x_opts.label, (x, origin.y - y_offset))?;
-        canvas.write_text_centred(self.y_opts.label, (origin.x - x_offset, y))
+        if !self.x_opts.hidden {
+            canvas.write_text_centred(self.x_opts.label, (x, origin.y - y_offset))?;
+        }
+        if !self.y_opts.hidden {
+            canvas.write_text_centred(self.y_opts.label, (origin.x - x_offset, y))?;
+        }
+
+        Ok(())
     }
 
 }
 
+fn write_category<C: Canvas>(category: Category, centre: Pixel, canvas: &mut C) -> Result<(), C::Err> {
+    match category {
+        Category::Name(name) => canvas.write_text_centred(name, centre),
+        Category::Value(value) => canvas.write_num_centred(value, centre),
+    }
+}
+
 impl<'a> Plottable for Axis<'a> {
     fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
         canvas.set_color(Color(0, 0, 0));
@@ -142,4 +198,23 @@ mod tests {
         assert_eq!(fake_canvas.shown, 0);
         assert_eq!(fake_canvas.cleared, 0)
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_hidden_and_categories() {
+        let mut fake_canvas = MockCanvas::new();
+        let mut dims = GraphDimensions::new(600.0, 600.0);
+        dims.max = GraphCoord::new(10.0, 3.0);
+        dims.min = GraphCoord::new(0.0, 0.0);
+
+        let x_opts = AxisOptions::default().hidden(true);
+        let y_opts = AxisOptions::default();
+
+        // only the categories on the graph should be drawn
+        let rows = [(1.0, Category::Name("build")), (2.0, Category::Name("test")), (5.0, Category::Name("deploy"))];
+        let axis = Axis::from_dimensions(&dims, &x_opts, &y_opts).categories(&[], &rows);
+
+        // the frame, then a tick and a gridline for each row but nothing along the x axis
+        assert_eq!(axis.plot(&dims, &mut fake_canvas), Ok(()));
+        assert_eq!(fake_canvas.lines.len(), 4 + 2 * 2);
+    }
+}
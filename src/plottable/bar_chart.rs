@@ -0,0 +1,200 @@
+use data_set::DataSet;
+use options::{BarLayout, Orientation};
+use plottable::Plottable;
+use plottable::primitives::Bar;
+use graph_dimensions::GraphDimensions;
+use canvas::Canvas;
+use pixel::GraphCoord;
+use utils;
+
+/// `BarChart` draws several `DataSet`s as one bar graph, points in
+/// different `DataSet`s with the same x value are either grouped side
+/// by side or stacked on top of each other depending on the layout
+pub struct BarChart<'a> {
+    data_sets: &'a [&'a DataSet<'a>],
+    layout: BarLayout,
+    orientation: Orientation,
+    width: f64,
+    baseline: f64,
+}
+
+impl <'a> BarChart<'a> {
+    pub fn new(data_sets: &'a [&'a DataSet<'a>]) -> Self {
+        BarChart {
+            data_sets: data_sets,
+            layout: BarLayout::Grouped,
+            orientation: Orientation::Vertical,
+            width: 0.8,
+            baseline: 0.0,
+        }
+    }
+
+    pub fn layout(mut self, layout: BarLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    pub fn orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    /// The width taken up by all of the bars at a single x value
+    pub fn width(mut self, width: f64) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// The value that every bar, or the bottom of every stack, starts from
+    pub fn baseline(mut self, baseline: f64) -> Self {
+        self.baseline = baseline;
+        self
+    }
+
+    /// Every bar that will be drawn along with the index of
+    /// the `DataSet` that it belongs to
+    pub fn bars(&self) -> Vec<(usize, Bar)> {
+        match self.layout {
+            BarLayout::Grouped => self.grouped_bars(),
+            BarLayout::Stacked => self.stacked_bars(false),
+            BarLayout::Normalised => self.stacked_bars(true),
+        }
+    }
+
+    fn grouped_bars(&self) -> Vec<(usize, Bar)> {
+        let count = self.data_sets.len() as f64;
+        let bar_width = self.width / count;
+
+        let mut bars = Vec::new();
+        for (n, ds) in self.data_sets.iter().enumerate() {
+            // offset each series so the whole group is centred on x
+            let offset = -self.width / 2.0 + bar_width * ((n as f64) + 0.5);
+
+            for point in ds.data_points.iter() {
+                let bar = Bar::new(point.x + offset, self.baseline, point.y, bar_width, self.orientation);
+                bars.push((n, bar));
+            }
+        }
+
+        bars
+    }
+
+    fn stacked_bars(&self, normalise: bool) -> Vec<(usize, Bar)> {
+        let mut bars = Vec::new();
+
+        for x in self.positions() {
+            let values = self.data_sets.iter()
+                .map(|ds| ds.data_points.iter().filter(|p| p.x == x).fold(0.0, |acc, p| acc + p.y))
+                .collect::<Vec<_>>();
+
+            let total = values.iter().fold(0.0, |acc, v: &f64| acc + v.abs());
+            let scale = if normalise && total > 0.0 { 100.0 / total } else { 1.0 };
+
+            // positive values are stacked upwards and negative values downwards
+            let mut above = self.baseline;
+            let mut below = self.baseline;
+            for (n, &value) in values.iter().enumerate() {
+                let value = value * scale;
+                if value == 0.0 {
+                    continue;
+                }
+
+                let start = if value > 0.0 { &mut above } else { &mut below };
+                let bar = Bar::new(x, *start, *start + value, self.width, self.orientation);
+                *start += value;
+                bars.push((n, bar));
+            }
+        }
+
+        bars
+    }
+
+    // every distinct x value in any of the data sets, in ascending order
+    fn positions(&self) -> Vec<f64> {
+        let mut xs = self.data_sets.iter()
+            .flat_map(|ds| ds.data_points.iter().map(|p| p.x))
+            .filter(|x| x.is_finite())
+            .collect::<Vec<_>>();
+
+        xs.sort_by(|a, b| a.partial_cmp(b).expect("x values should be finite"));
+        xs.dedup();
+        xs
+    }
+}
+
+impl <'a> Plottable for BarChart<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        for (n, bar) in self.bars() {
+            canvas.set_color(self.data_sets[n].choose_color());
+            bar.plot(bounds, canvas)?;
+        }
+
+        Ok(())
+    }
+
+    fn data_extent(&self) -> Option<(GraphCoord, GraphCoord)> {
+        let corners = self.bars().iter()
+            .flat_map(|&(_, bar)| {
+                let (min, max) = bar.corners();
+                vec!(min, max)
+            })
+            .collect::<Vec<_>>();
+
+        if corners.is_empty() {
+            return None;
+        }
+
+        Some((utils::get_min_coord(&corners), utils::get_max_coord(&corners)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_set::DataSet;
+    use options::{DataSetOptions, BarLayout, Orientation};
+    use pixel::GraphCoord;
+    use plottable::Plottable;
+
+    #[test]
+    fn test_grouped() {
+        let opts = DataSetOptions::default();
+        let a = DataSet::from_vecs(vec!(1.0), vec!(3.0), &opts).unwrap();
+        let b = DataSet::from_vecs(vec!(1.0), vec!(-2.0), &opts).unwrap();
+        let data_sets = [&a, &b];
+
+        let bars = BarChart::new(&data_sets).width(1.0).bars();
+        assert_eq!(bars, vec!(
+            (0, Bar::new(0.75, 0.0, 3.0, 0.5, Orientation::Vertical)),
+            (1, Bar::new(1.25, 0.0, -2.0, 0.5, Orientation::Vertical)),
+        ));
+    }
+
+    #[test]
+    fn test_stacked() {
+        let opts = DataSetOptions::default();
+        let a = DataSet::from_vecs(vec!(1.0, 2.0), vec!(3.0, 1.0), &opts).unwrap();
+        let b = DataSet::from_vecs(vec!(1.0, 2.0), vec!(1.0, -1.0), &opts).unwrap();
+        let data_sets = [&a, &b];
+
+        let chart = BarChart::new(&data_sets).layout(BarLayout::Stacked).width(1.0);
+        let bars = chart.bars().into_iter().map(|(n, bar)| (n, bar.start, bar.end)).collect::<Vec<_>>();
+
+        // negative values should stack down from the baseline
+        assert_eq!(bars, vec!((0, 0.0, 3.0), (1, 3.0, 4.0), (0, 0.0, 1.0), (1, 0.0, -1.0)));
+        assert_eq!(chart.data_extent(), Some((GraphCoord::new(0.5, -1.0), GraphCoord::new(2.5, 4.0))));
+    }
+
+    #[test]
+    fn test_normalised() {
+        let opts = DataSetOptions::default();
+        let a = DataSet::from_vecs(vec!(1.0), vec!(3.0), &opts).unwrap();
+        let b = DataSet::from_vecs(vec!(1.0), vec!(1.0), &opts).unwrap();
+        let data_sets = [&a, &b];
+
+        let bars = BarChart::new(&data_sets).layout(BarLayout::Normalised).bars();
+        let ends = bars.iter().map(|&(_, bar)| bar.end).collect::<Vec<_>>();
+
+        assert_eq!(ends, vec!(75.0, 100.0));
+    }
+}
@@ -0,0 +1,211 @@
+use grid::Grid;
+use colormap::Colormap;
+use labeller::Labeller;
+use marching_squares;
+use plottable::Plottable;
+use plottable::primitives::{Line, Polygon};
+use graph_dimensions::GraphDimensions;
+use canvas::Canvas;
+use pixel::{Color, GraphCoord};
+
+/// `ContourSeries` draws the iso-lines of a `Grid`, either at levels
+/// chosen by the `Labeller` or at levels given by the user
+/// In filled mode the bands between each level are filled using the colormap
+pub struct ContourSeries<'a> {
+    grid: &'a Grid,
+    levels: Option<Vec<f64>>,
+    level_count: i32,
+    colormap: Colormap,
+    color: Option<Color>,
+    filled: bool,
+    labels: bool,
+}
+
+impl <'a> ContourSeries<'a> {
+    pub fn new(grid: &'a Grid) -> Self {
+        ContourSeries {
+            grid: grid,
+            levels: None,
+            level_count: 8,
+            colormap: Colormap::default(),
+            color: None,
+            filled: false,
+            labels: false,
+        }
+    }
+
+    /// Draws the iso-lines at exactly these levels instead of
+    /// letting the `Labeller` choose them
+    pub fn levels(mut self, levels: Vec<f64>) -> Self {
+        self.levels = Some(levels);
+        self
+    }
+
+    /// The amount of levels the `Labeller` should aim for
+    /// when choosing the levels automatically
+    pub fn level_count(mut self, level_count: i32) -> Self {
+        self.level_count = level_count;
+        self
+    }
+
+    pub fn colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = colormap;
+        self
+    }
+
+    /// Draws every iso-line in one colour instead of using the colormap
+    pub fn color<C: Into<Option<Color>>>(mut self, color: C) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    pub fn filled(mut self, filled: bool) -> Self {
+        self.filled = filled;
+        self
+    }
+
+    /// Writes the value of each level next to its iso-line
+    pub fn labels(mut self, labels: bool) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// The levels that will be drawn, in ascending order
+    pub fn get_levels(&self) -> Vec<f64> {
+        let (min, max) = (self.grid.min_value(), self.grid.max_value());
+
+        let mut levels = match self.levels {
+            Some(ref levels) => levels.clone(),
+            None => {
+                if max <= min {
+                    return vec!();
+                }
+
+                let label = Labeller::in_base10().search(min, max, self.level_count);
+                let mut levels = Vec::new();
+                let mut level = label.min;
+                while label.step > 0.0 && level <= label.max {
+                    if level > min && level < max {
+                        levels.push(level);
+                    }
+                    level += label.step;
+                }
+                levels
+            }
+        };
+
+        levels.sort_by(|a, b| a.partial_cmp(b).expect("levels should not be NaN"));
+        levels
+    }
+
+    fn plot_bands<C: Canvas>(&self, levels: &[f64], bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let (min, max) = (self.grid.min_value(), self.grid.max_value());
+
+        let mut edges = vec!(min);
+        edges.extend(levels.iter().cloned().filter(|&l| l > min && l < max));
+        edges.push(max);
+
+        let bands = edges.len() - 1;
+        for (n, pair) in edges.windows(2).enumerate() {
+            let t = if bands > 1 { (n as f64) / ((bands - 1) as f64) } else { 0.5 };
+            canvas.set_color(self.colormap.color_at(t));
+
+            for polygon in marching_squares::band_polygons(self.grid, pair[0], pair[1]) {
+                Polygon(polygon).plot(bounds, canvas)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn plot_label<C: Canvas>(&self, level: f64, lines: &[Line], bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        if lines.is_empty() {
+            return Ok(());
+        }
+
+        let Line(start, end) = lines[lines.len() / 2];
+        let middle = GraphCoord::new((start.x + end.x) / 2.0, (start.y + end.y) / 2.0);
+        let pix = try_opt!(bounds.convert_to_pixel(middle));
+
+        canvas.write_num_centred(level, pix)
+    }
+}
+
+impl <'a> Plottable for ContourSeries<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let levels = self.get_levels();
+
+        if self.filled {
+            self.plot_bands(&levels, bounds, canvas)?;
+        }
+
+        for (n, &level) in levels.iter().enumerate() {
+            let color = match self.color {
+                Some(color) => color,
+                // the bands already show the colormap so keep the lines plain
+                None if self.filled => Color(0, 0, 0),
+                None => self.colormap.color_at((n as f64) / ((levels.len().max(2) - 1) as f64)),
+            };
+            canvas.set_color(color);
+
+            let lines = marching_squares::iso_lines(self.grid, level);
+            for line in lines.iter() {
+                line.plot(bounds, canvas)?;
+            }
+
+            if self.labels {
+                self.plot_label(level, &lines, bounds, canvas)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn data_extent(&self) -> Option<(GraphCoord, GraphCoord)> {
+        Some((self.grid.get_min_coord(), self.grid.get_max_coord()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::mock_canvas::MockCanvas;
+    use graph_dimensions::GraphDimensions;
+    use grid::Grid;
+    use pixel::GraphCoord;
+    use plottable::Plottable;
+
+    #[test]
+    fn test_levels() {
+        let xs = (0..11).map(|i| i as f64).collect::<Vec<_>>();
+        let grid = Grid::from_fn(xs.clone(), xs, |x, y| x + y);
+
+        // automatic levels should all be strictly inside the range of the grid
+        let levels = ContourSeries::new(&grid).get_levels();
+        assert!(!levels.is_empty());
+        assert!(levels.iter().all(|&l| l > 0.0 && l < 20.0));
+
+        // user levels should be used as given, but sorted
+        let levels = ContourSeries::new(&grid).levels(vec!(15.0, 5.0)).get_levels();
+        assert_eq!(levels, vec!(5.0, 15.0));
+    }
+
+    #[test]
+    fn test_plot() {
+        let mut fake_canvas = MockCanvas::new();
+        let xs = (0..11).map(|i| i as f64).collect::<Vec<_>>();
+        let grid = Grid::from_fn(xs.clone(), xs, |x, y| x * y);
+
+        let mut dims = GraphDimensions::new(600.0, 600.0);
+        dims.min = GraphCoord::new(0.0, 0.0);
+        dims.max = GraphCoord::new(10.0, 10.0);
+
+        let contour = ContourSeries::new(&grid).filled(true).labels(true);
+        assert_eq!(contour.data_extent(), Some((dims.min, dims.max)));
+        assert_eq!(contour.plot(&dims, &mut fake_canvas), Ok(()));
+
+        // contours should never clear or show the canvas
+        assert_eq!(fake_canvas.shown, 0);
+        assert_eq!(fake_canvas.cleared, 0);
+    }
+}
@@ -0,0 +1,311 @@
+use data_set::DataSet;
+use colormap::Colormap;
+use options::Orientation;
+use plottable::Plottable;
+use plottable::primitives::{Bar, Polygon};
+use graph_dimensions::GraphDimensions;
+use canvas::Canvas;
+use pixel::{Color, GraphCoord};
+use std::collections::HashMap;
+use std::f64;
+
+// Picks the colour of a cell from how many points it holds
+fn count_color(colormap: Colormap, log: bool, count: u32, max_count: u32) -> Color {
+    if log {
+        colormap.color_for((count as f64).ln(), 0.0, (max_count as f64).ln())
+    } else {
+        colormap.color_for(count as f64, 0.0, max_count as f64)
+    }
+}
+
+fn finite_points(ds: &DataSet) -> Vec<GraphCoord> {
+    ds.data_points.iter().cloned().filter(|p| p.x.is_finite() && p.y.is_finite()).collect()
+}
+
+/// `Hist2dSeries` splits the area covered by a `DataSet` into rectangular bins
+/// and colours each bin by the amount of points inside of it
+/// Bins without any points aren't drawn
+pub struct Hist2dSeries<'a> {
+    ds: &'a DataSet<'a>,
+    bins: (usize, usize),
+    colormap: Colormap,
+    log: bool,
+}
+
+impl <'a> Hist2dSeries<'a> {
+    pub fn new(ds: &'a DataSet<'a>) -> Self {
+        Hist2dSeries {
+            ds: ds,
+            bins: (50, 50),
+            colormap: Colormap::default(),
+            log: false,
+        }
+    }
+
+    /// The amount of bins along the x and y axis
+    pub fn bins(mut self, x_bins: usize, y_bins: usize) -> Self {
+        self.bins = (x_bins.max(1), y_bins.max(1));
+        self
+    }
+
+    pub fn colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = colormap;
+        self
+    }
+
+    /// Colours each bin by the log of its count so sparse bins are still visible
+    pub fn log(mut self, log: bool) -> Self {
+        self.log = log;
+        self
+    }
+
+    /// The amount of points in each bin, `counts[j][i]` is the
+    /// i-th bin along x and the j-th bin along y
+    pub fn counts(&self) -> Vec<Vec<u32>> {
+        let (x_bins, y_bins) = self.bins;
+        let mut counts = vec!(vec!(0; x_bins); y_bins);
+        let (min, max) = (self.ds.get_min_coord(), self.ds.get_max_coord());
+
+        let bin = |v: f64, min: f64, max: f64, bins: usize| {
+            if max <= min {
+                return 0;
+            }
+            // the largest value goes in the last bin rather than one past it
+            (((v - min) / (max - min) * (bins as f64)) as usize).min(bins - 1)
+        };
+
+        for p in finite_points(self.ds) {
+            counts[bin(p.y, min.y, max.y, y_bins)][bin(p.x, min.x, max.x, x_bins)] += 1;
+        }
+
+        counts
+    }
+}
+
+impl <'a> Plottable for Hist2dSeries<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let counts = self.counts();
+        let max_count = counts.iter().flat_map(|row| row.iter()).fold(0, |acc, &c| acc.max(c));
+
+        let (x_bins, y_bins) = self.bins;
+        let (min, max) = (self.ds.get_min_coord(), self.ds.get_max_coord());
+        let width = (max.x - min.x) / (x_bins as f64);
+        let height = (max.y - min.y) / (y_bins as f64);
+
+        for (j, row) in counts.iter().enumerate() {
+            for (i, &count) in row.iter().enumerate() {
+                if count == 0 {
+                    continue;
+                }
+
+                canvas.set_color(count_color(self.colormap, self.log, count, max_count));
+                let x = min.x + width * ((i as f64) + 0.5);
+                let y = min.y + height * (j as f64);
+                Bar::new(x, y, y + height, width, Orientation::Vertical).plot(bounds, canvas)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn data_extent(&self) -> Option<(GraphCoord, GraphCoord)> {
+        if self.ds.data_points.is_empty() {
+            return None;
+        }
+        Some((self.ds.get_min_coord(), self.ds.get_max_coord()))
+    }
+}
+
+/// `HexbinSeries` is the same as a `Hist2dSeries` except the bins
+/// are hexagons, which follow the shape of the data more closely
+pub struct HexbinSeries<'a> {
+    ds: &'a DataSet<'a>,
+    gridsize: usize,
+    colormap: Colormap,
+    log: bool,
+}
+
+impl <'a> HexbinSeries<'a> {
+    pub fn new(ds: &'a DataSet<'a>) -> Self {
+        HexbinSeries {
+            ds: ds,
+            gridsize: 30,
+            colormap: Colormap::default(),
+            log: false,
+        }
+    }
+
+    /// The amount of hexagons along the x axis, the amount along the
+    /// y axis is chosen so the hexagons are regular on a square graph
+    pub fn gridsize(mut self, gridsize: usize) -> Self {
+        self.gridsize = gridsize.max(1);
+        self
+    }
+
+    pub fn colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = colormap;
+        self
+    }
+
+    /// Colours each hexagon by the log of its count so sparse hexagons are still visible
+    pub fn log(mut self, log: bool) -> Self {
+        self.log = log;
+        self
+    }
+
+    // the size of the grid that the centres of the hexagons lie on
+    fn spacing(&self) -> (f64, f64) {
+        let (min, max) = (self.ds.get_min_coord(), self.ds.get_max_coord());
+        let x_count = self.gridsize as f64;
+        let y_count = (x_count / 3f64.sqrt()).floor().max(1.0);
+
+        let width = if max.x > min.x { (max.x - min.x) / x_count } else { 1.0 };
+        let height = if max.y > min.y { (max.y - min.y) / y_count } else { 1.0 };
+        (width, height)
+    }
+
+    /// The centre of every hexagon with at least one point in it
+    /// and how many points are inside
+    ///
+    /// The hexagons lie on two rectangular grids, the second offset by half a
+    /// cell, each point goes in the hexagon of whichever grid has the nearest centre
+    pub fn counts(&self) -> Vec<(GraphCoord, u32)> {
+        let min = self.ds.get_min_coord();
+        let (width, height) = self.spacing();
+        let mut counts = HashMap::new();
+
+        for p in finite_points(self.ds) {
+            let x = (p.x - min.x) / width;
+            let y = (p.y - min.y) / height;
+
+            let (x1, y1) = (x.round(), y.round());
+            let (x2, y2) = (x.floor() + 0.5, y.floor() + 0.5);
+
+            let d1 = (x - x1).powi(2) + 3.0 * (y - y1).powi(2);
+            let d2 = (x - x2).powi(2) + 3.0 * (y - y2).powi(2);
+
+            // the centres are doubled so both grids can be keyed by integers
+            let key = if d1 < d2 {
+                ((x1 * 2.0) as i64, (y1 * 2.0) as i64)
+            } else {
+                ((x2 * 2.0) as i64, (y2 * 2.0) as i64)
+            };
+            *counts.entry(key).or_insert(0) += 1;
+        }
+
+        let mut centres = counts.into_iter().map(|((i, j), count)| {
+            let centre = GraphCoord::new(min.x + (i as f64) * width / 2.0, min.y + (j as f64) * height / 2.0);
+            (centre, count)
+        }).collect::<Vec<_>>();
+
+        // keep the order the same each time the hexagons are drawn
+        centres.sort_by(|a, b| (a.0.y, a.0.x).partial_cmp(&(b.0.y, b.0.x)).expect("centres should be finite"));
+        centres
+    }
+
+    /// The corners of the hexagon centred on `centre`
+    pub fn hexagon(&self, centre: GraphCoord) -> Vec<GraphCoord> {
+        let (width, height) = self.spacing();
+        let offsets = [(0.5, -0.5), (0.5, 0.5), (0.0, 1.0), (-0.5, 0.5), (-0.5, -0.5), (0.0, -1.0)];
+
+        offsets.iter()
+            .map(|&(dx, dy)| GraphCoord::new(centre.x + dx * width, centre.y + dy * height / 3.0))
+            .collect()
+    }
+}
+
+impl <'a> Plottable for HexbinSeries<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let counts = self.counts();
+        let max_count = counts.iter().fold(0, |acc, &(_, c)| acc.max(c));
+
+        for &(centre, count) in counts.iter() {
+            canvas.set_color(count_color(self.colormap, self.log, count, max_count));
+            Polygon(self.hexagon(centre)).plot(bounds, canvas)?;
+        }
+
+        Ok(())
+    }
+
+    fn data_extent(&self) -> Option<(GraphCoord, GraphCoord)> {
+        if self.ds.data_points.is_empty() {
+            return None;
+        }
+        Some((self.ds.get_min_coord(), self.ds.get_max_coord()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::mock_canvas::MockCanvas;
+    use data_set::DataSet;
+    use graph_dimensions::GraphDimensions;
+    use options::DataSetOptions;
+    use pixel::GraphCoord;
+    use plottable::Plottable;
+
+    fn points<'a>(opts: &'a DataSetOptions<'a>) -> DataSet<'a> {
+        let xs = (0..100).map(|n| ((n * 37) % 100) as f64 / 10.0).collect::<Vec<_>>();
+        let ys = (0..100).map(|n| ((n * 61) % 100) as f64 / 10.0).collect::<Vec<_>>();
+        DataSet::from_vecs(xs, ys, opts).unwrap()
+    }
+
+    #[test]
+    fn test_hist2d_counts() {
+        let opts = DataSetOptions::default();
+        let ds = DataSet::from_vecs(vec!(0.0, 0.1, 1.0, 2.0), vec!(0.0, 0.1, 1.0, 2.0), &opts).unwrap();
+
+        // the largest point should be in the last bin, not off the end
+        let counts = Hist2dSeries::new(&ds).bins(2, 2).counts();
+        assert_eq!(counts, vec!(vec!(2, 0), vec!(0, 2)));
+
+        let ds = points(&opts);
+        let counts = Hist2dSeries::new(&ds).bins(7, 5).counts();
+        assert_eq!(counts.iter().flat_map(|row| row.iter()).sum::<u32>(), 100);
+    }
+
+    #[test]
+    fn test_hexbin_counts() {
+        let opts = DataSetOptions::default();
+        let ds = points(&opts);
+        let hexbin = HexbinSeries::new(&ds).gridsize(5);
+
+        // every point should be counted exactly once
+        let counts = hexbin.counts();
+        assert_eq!(counts.iter().fold(0, |acc, &(_, c)| acc + c), 100);
+
+        // and each point should be inside of, or on the edge of, the hexagon it was put in
+        let ds = DataSet::from_vecs(vec!(0.0, 10.0, 3.3), vec!(0.0, 10.0, 6.1), &opts).unwrap();
+        let hexbin = HexbinSeries::new(&ds).gridsize(5);
+        let (width, height) = hexbin.spacing();
+        for &(centre, _) in hexbin.counts().iter() {
+            let inside = ds.data_points.iter().any(|p| {
+                let dx = (p.x - centre.x).abs() / width;
+                let dy = (p.y - centre.y).abs() / (height / 3.0);
+                dx <= 0.5 + 1e-9 && dy <= 1.0 - dx + 1e-9
+            });
+            assert!(inside);
+        }
+    }
+
+    #[test]
+    fn test_plot() {
+        let mut fake_canvas = MockCanvas::new();
+        let opts = DataSetOptions::default();
+        let ds = points(&opts);
+
+        let hist = Hist2dSeries::new(&ds).log(true);
+        let hexbin = HexbinSeries::new(&ds);
+        let (min, max) = hist.data_extent().unwrap();
+        assert_eq!(hexbin.data_extent(), Some((min, max)));
+
+        let mut dims = GraphDimensions::new(600.0, 600.0);
+        dims.min = min;
+        dims.max = max;
+
+        assert_eq!(hist.plot(&dims, &mut fake_canvas), Ok(()));
+        assert_eq!(hexbin.plot(&dims, &mut fake_canvas), Ok(()));
+        assert_eq!(dims.min, GraphCoord::new(0.0, 0.0));
+    }
+}
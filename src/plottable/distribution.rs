@@ -0,0 +1,299 @@
+use data_set::DataSet;
+use options::{DataSetOptions, PointStyle, StepMode};
+use plottable::{Plottable, LegendEntry};
+use plottable::graphs::StepSeries;
+use plottable::primitives::{Line, Point};
+use graph_dimensions::GraphDimensions;
+use canvas::Canvas;
+use pixel::{Color, GraphCoord};
+use stats;
+use utils;
+
+/// `EcdfSeries` draws the empirical cumulative distribution of some samples,
+/// a step up by 1/n at each sample from 0 below the smallest to 1 at the largest
+pub struct EcdfSeries<'a> {
+    samples: Vec<f64>,
+    opts: &'a DataSetOptions<'a>,
+    complementary: bool,
+    log_x: bool,
+    log_y: bool,
+}
+
+impl <'a> EcdfSeries<'a> {
+    pub fn new(samples: &[f64], opts: &'a DataSetOptions<'a>) -> Self {
+        EcdfSeries {
+            samples: stats::sorted(samples),
+            opts: opts,
+            complementary: false,
+            log_x: false,
+            log_y: false,
+        }
+    }
+
+    /// Draws 1 - F instead, the fraction of samples larger than each value
+    /// which makes the tail of a distribution easier to see
+    pub fn complementary(mut self, complementary: bool) -> Self {
+        self.complementary = complementary;
+        self
+    }
+
+    /// Plots the log10 of each sample, anything that isn't positive is left out
+    /// The x axis is then in log10 units, so a sample of 100 is at 2 on the axis
+    pub fn log_x(mut self, log_x: bool) -> Self {
+        self.log_x = log_x;
+        self
+    }
+
+    /// Plots the log10 of each probability, a probability of 0 is left out
+    /// The y axis is then in log10 units, so a probability of 0.01 is at -2 on the axis
+    pub fn log_y(mut self, log_y: bool) -> Self {
+        self.log_y = log_y;
+        self
+    }
+
+    /// The point at the top of each step, starting from the
+    /// bottom of the first step
+    pub fn points(&self) -> Vec<GraphCoord> {
+        let n = self.samples.len() as f64;
+        let probability = |count: usize| {
+            let p = (count as f64) / n;
+            if self.complementary { 1.0 - p } else { p }
+        };
+
+        let mut points = Vec::new();
+        if let Some(&first) = self.samples.first() {
+            points.push(GraphCoord::new(first, probability(0)));
+        }
+
+        for (i, &x) in self.samples.iter().enumerate() {
+            // equal samples are a single step so only the last of them is kept
+            if self.samples.get(i + 1) == Some(&x) {
+                continue;
+            }
+            points.push(GraphCoord::new(x, probability(i + 1)));
+        }
+
+        points.into_iter()
+            .map(|p| GraphCoord::new(if self.log_x { p.x.log10() } else { p.x },
+                                     if self.log_y { p.y.log10() } else { p.y }))
+            .filter(|p| p.x.is_finite() && p.y.is_finite())
+            .collect()
+    }
+}
+
+impl <'a> Plottable for EcdfSeries<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let ds = DataSet::new(self.points(), self.opts);
+        StepSeries(&ds, StepMode::Post).plot(bounds, canvas)
+    }
+
+    fn data_extent(&self) -> Option<(GraphCoord, GraphCoord)> {
+        let points = self.points();
+        if points.is_empty() {
+            return None;
+        }
+        Some((utils::get_min_coord(&points), utils::get_max_coord(&points)))
+    }
+
+    fn legend_entries(&self) -> Vec<LegendEntry<'_>> {
+        vec!(LegendEntry::new(self.opts.name, self.opts.color))
+    }
+}
+
+/// What the samples of a `QqSeries` are compared against
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Distribution<'a> {
+    /// The standard normal distribution
+    Normal,
+    /// The uniform distribution between 0 and 1
+    Uniform,
+    /// The exponential distribution with a rate of 1
+    Exponential,
+    /// The quantiles of another set of samples
+    Sample(&'a [f64]),
+}
+
+/// `QqSeries` plots the quantiles of some samples against the same quantiles of a
+/// `Distribution`, if the samples follow the distribution the points lie on a
+/// straight line, which is drawn through the first and third quartiles
+pub struct QqSeries<'a> {
+    samples: Vec<f64>,
+    distribution: Distribution<'a>,
+    opts: &'a DataSetOptions<'a>,
+    line_color: Color,
+}
+
+impl <'a> QqSeries<'a> {
+    pub fn new(samples: &[f64], distribution: Distribution<'a>, opts: &'a DataSetOptions<'a>) -> Self {
+        QqSeries {
+            samples: stats::sorted(samples),
+            distribution: distribution,
+            opts: opts,
+            line_color: Color(128, 128, 128),
+        }
+    }
+
+    /// The colour of the reference line
+    pub fn line_color(mut self, color: Color) -> Self {
+        self.line_color = color;
+        self
+    }
+
+    fn theoretical(&self, p: f64) -> Option<f64> {
+        match self.distribution {
+            Distribution::Normal => Some(stats::normal_quantile(p)),
+            Distribution::Uniform => Some(stats::uniform_quantile(p)),
+            Distribution::Exponential => Some(stats::exponential_quantile(p)),
+            Distribution::Sample(other) => stats::quantile(&stats::sorted(other), p),
+        }
+    }
+
+    /// Each point has the theoretical quantile as x and the sample quantile as y
+    /// when comparing two samples the smaller one decides how many points there are
+    pub fn points(&self) -> Vec<GraphCoord> {
+        let xs = match self.distribution {
+            Distribution::Sample(other) => {
+                let other = stats::sorted(other);
+                QqSeries::quantiles(&other, other.len().min(self.samples.len()))
+            },
+            _ => stats::plotting_positions(self.samples.len()).into_iter()
+                .filter_map(|p| self.theoretical(p))
+                .collect(),
+        };
+        let ys = QqSeries::quantiles(&self.samples, xs.len());
+
+        xs.into_iter().zip(ys)
+            .map(|(x, y)| GraphCoord::new(x, y))
+            .filter(|p| p.x.is_finite() && p.y.is_finite())
+            .collect()
+    }
+
+    // `n` quantiles of some sorted values, which are just the values
+    // themselves unless there are more values than quantiles
+    fn quantiles(sorted: &[f64], n: usize) -> Vec<f64> {
+        if sorted.len() == n {
+            return sorted.to_vec();
+        }
+
+        stats::plotting_positions(n).into_iter()
+            .filter_map(|p| stats::quantile(sorted, p))
+            .collect()
+    }
+
+    /// The slope and intercept of the line through the first and third quartiles
+    pub fn reference_line(&self) -> Option<(f64, f64)> {
+        let (x1, x3) = (self.theoretical(0.25)?, self.theoretical(0.75)?);
+        let (y1, y3) = (stats::quantile(&self.samples, 0.25)?, stats::quantile(&self.samples, 0.75)?);
+        if x3 == x1 {
+            return None;
+        }
+
+        let slope = (y3 - y1) / (x3 - x1);
+        Some((slope, y1 - slope * x1))
+    }
+}
+
+impl <'a> Plottable for QqSeries<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let points = self.points();
+
+        // the line runs between the first and last point so it is always on the graph
+        if let (Some((slope, intercept)), Some(first), Some(last)) = (self.reference_line(), points.first(), points.last()) {
+            let at = |x: f64| GraphCoord::new(x, slope * x + intercept);
+            canvas.set_color(self.line_color);
+            Line(at(first.x), at(last.x)).plot(bounds, canvas)?;
+        }
+
+        // a Q-Q plot should always have a marker for each point
+        let style = match self.opts.point_style {
+            PointStyle::Nothing => PointStyle::Cross,
+            style => style,
+        };
+
+        canvas.set_color(self.opts.color);
+        for &point in points.iter() {
+            Point(point, style).plot(bounds, canvas)?;
+        }
+
+        Ok(())
+    }
+
+    fn data_extent(&self) -> Option<(GraphCoord, GraphCoord)> {
+        let mut points = self.points();
+        if points.is_empty() {
+            return None;
+        }
+
+        if let Some((slope, intercept)) = self.reference_line() {
+            let ends = [points[0].x, points[points.len() - 1].x];
+            points.extend(ends.iter().map(|&x| GraphCoord::new(x, slope * x + intercept)));
+        }
+        Some((utils::get_min_coord(&points), utils::get_max_coord(&points)))
+    }
+
+    fn legend_entries(&self) -> Vec<LegendEntry<'_>> {
+        vec!(LegendEntry::new(self.opts.name, self.opts.color))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::mock_canvas::MockCanvas;
+    use graph_dimensions::GraphDimensions;
+    use options::DataSetOptions;
+    use pixel::GraphCoord;
+    use plottable::Plottable;
+
+    #[test]
+    fn test_ecdf() {
+        let opts = DataSetOptions::default();
+        let samples = [3.0, 1.0, 2.0, 2.0];
+
+        let ecdf = EcdfSeries::new(&samples, &opts);
+        assert_eq!(ecdf.points(), vec!(GraphCoord::new(1.0, 0.0), GraphCoord::new(1.0, 0.25),
+                                       GraphCoord::new(2.0, 0.75), GraphCoord::new(3.0, 1.0)));
+
+        // 1 - F has no log at the last sample
+        let ecdf = EcdfSeries::new(&samples, &opts).complementary(true).log_y(true);
+        let points = ecdf.points();
+        assert_eq!(points.len(), 3);
+        assert_eq!(points[0], GraphCoord::new(1.0, 0.0));
+        assert_eq!(points[2], GraphCoord::new(2.0, 0.25f64.log10()));
+    }
+
+    #[test]
+    fn test_qq() {
+        let opts = DataSetOptions::default();
+        let samples = (0..100).map(|n| 5.0 + 2.0 * ::stats::normal_quantile((n as f64 + 0.5) / 100.0)).collect::<Vec<_>>();
+
+        // normal samples against the normal distribution should lie on the reference line
+        let qq = QqSeries::new(&samples, Distribution::Normal, &opts);
+        let (slope, intercept) = qq.reference_line().unwrap();
+        assert!((slope - 2.0).abs() < 0.05 && (intercept - 5.0).abs() < 1e-6);
+        assert!(qq.points().iter().all(|p| (p.y - (slope * p.x + intercept)).abs() < 0.2));
+
+        // two samples are compared at as many quantiles as the smaller one has
+        let other = [1.0, 2.0, 3.0];
+        assert_eq!(QqSeries::new(&samples, Distribution::Sample(&other), &opts).points().len(), 3);
+    }
+
+    #[test]
+    fn test_plot() {
+        let mut fake_canvas = MockCanvas::new();
+        let opts = DataSetOptions::default();
+        let samples = [0.2, 1.5, 0.7, 3.1, 0.1, 0.9];
+
+        let ecdf = EcdfSeries::new(&samples, &opts).log_x(true);
+        let qq = QqSeries::new(&samples, Distribution::Exponential, &opts);
+
+        for &(min, max) in [ecdf.data_extent().unwrap(), qq.data_extent().unwrap()].iter() {
+            let mut dims = GraphDimensions::new(600.0, 600.0);
+            dims.min = min;
+            dims.max = max;
+
+            assert_eq!(ecdf.plot(&dims, &mut fake_canvas), Ok(()));
+            assert_eq!(qq.plot(&dims, &mut fake_canvas), Ok(()));
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use data_set::DataSet;
+use plottable::Plottable;
+use plottable::primitives::Polygon;
+use graph_dimensions::GraphDimensions;
+use canvas::Canvas;
+use pixel::{Color, GraphCoord};
+use utils;
+use std::f64;
+
+/// `FillBetween` fills the region between a `DataSet` and a baseline
+/// or between two `DataSet`s, only the x range covered by both is filled
+/// and where their x values differ the other series is interpolated
+pub struct FillBetween<'a> {
+    upper: &'a DataSet<'a>,
+    lower: Option<&'a DataSet<'a>>,
+    baseline: f64,
+    color: Option<Color>,
+    alpha: u8,
+}
+
+impl <'a> FillBetween<'a> {
+    /// Fills between `ds` and its baseline as given in its options
+    pub fn new(ds: &'a DataSet<'a>) -> Self {
+        FillBetween {
+            upper: ds,
+            lower: None,
+            baseline: ds.options.baseline,
+            color: ds.options.fill_color,
+            alpha: ds.options.fill_alpha,
+        }
+    }
+
+    /// Fills between two `DataSet`s, the colour and opacity
+    /// are taken from the options of `upper`
+    pub fn between(upper: &'a DataSet<'a>, lower: &'a DataSet<'a>) -> Self {
+        FillBetween {
+            lower: Some(lower),
+            ..FillBetween::new(upper)
+        }
+    }
+
+    pub fn baseline(mut self, baseline: f64) -> Self {
+        self.baseline = baseline;
+        self
+    }
+
+    /// The colour of the fill, if this is `None` the colour of
+    /// the upper `DataSet` is used
+    pub fn color<C: Into<Option<Color>>>(mut self, color: C) -> Self {
+        self.color = color.into();
+        self
+    }
+
+    /// The opacity of the fill, 255 is fully opaque
+    pub fn alpha(mut self, alpha: u8) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    /// The outline of the region to be filled, going forwards along
+    /// the upper series and back along the lower series
+    pub fn outline(&self) -> Vec<GraphCoord> {
+        let upper = sorted_points(self.upper);
+        let lower = match self.lower {
+            Some(ds) => sorted_points(ds),
+            None => {
+                let (min, max) = (self.upper.get_min_coord(), self.upper.get_max_coord());
+                vec!(GraphCoord::new(min.x, self.baseline), GraphCoord::new(max.x, self.baseline))
+            },
+        };
+
+        if upper.is_empty() || lower.is_empty() {
+            return vec!();
+        }
+
+        let start = upper[0].x.max(lower[0].x);
+        let end = upper[upper.len() - 1].x.min(lower[lower.len() - 1].x);
+        if start > end {
+            return vec!();
+        }
+
+        let mut xs = upper.iter().chain(lower.iter())
+            .map(|p| p.x)
+            .filter(|&x| x >= start && x <= end)
+            .collect::<Vec<_>>();
+        xs.push(start);
+        xs.push(end);
+        xs.sort_by(|a, b| a.partial_cmp(b).expect("x values should not be NaN"));
+        xs.dedup();
+
+        let along = |points: &[GraphCoord], x: f64| {
+            utils::interpolate_y(points, x).map(|y| GraphCoord::new(x, y))
+        };
+
+        let mut outline = xs.iter().filter_map(|&x| along(&upper, x)).collect::<Vec<_>>();
+        outline.extend(xs.iter().rev().filter_map(|&x| along(&lower, x)));
+        outline
+    }
+}
+
+fn sorted_points(ds: &DataSet) -> Vec<GraphCoord> {
+    let mut points = ds.data_points.iter()
+        .cloned()
+        .filter(|p| p.x.is_finite() && p.y.is_finite())
+        .collect::<Vec<_>>();
+    points.sort_by(|a, b| a.x.partial_cmp(&b.x).expect("x values should be finite"));
+    points
+}
+
+impl <'a> Plottable for FillBetween<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        canvas.set_color(self.color.unwrap_or(self.upper.choose_color()));
+        canvas.set_alpha(self.alpha);
+
+        let res = Polygon(self.outline()).plot(bounds, canvas);
+
+        canvas.set_alpha(255);
+        res
+    }
+
+    fn data_extent(&self) -> Option<(GraphCoord, GraphCoord)> {
+        let outline = self.outline();
+        if outline.is_empty() {
+            return None;
+        }
+
+        Some((utils::get_min_coord(&outline), utils::get_max_coord(&outline)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::mock_canvas::MockCanvas;
+    use data_set::DataSet;
+    use graph_dimensions::GraphDimensions;
+    use options::DataSetOptions;
+    use pixel::GraphCoord;
+    use plottable::Plottable;
+
+    #[test]
+    fn test_outline_baseline() {
+        let opts = DataSetOptions::default().baseline(-1.0);
+        let ds = DataSet::from_vecs(vec!(0.0, 1.0, 2.0), vec!(1.0, 2.0, 1.0), &opts).unwrap();
+
+        let outline = FillBetween::new(&ds).outline();
+        assert_eq!(outline, vec!(
+            GraphCoord::new(0.0, 1.0), GraphCoord::new(1.0, 2.0), GraphCoord::new(2.0, 1.0),
+            GraphCoord::new(2.0, -1.0), GraphCoord::new(1.0, -1.0), GraphCoord::new(0.0, -1.0),
+        ));
+    }
+
+    #[test]
+    fn test_outline_between() {
+        let opts = DataSetOptions::default();
+        let upper = DataSet::from_vecs(vec!(0.0, 2.0, 4.0), vec!(4.0, 4.0, 4.0), &opts).unwrap();
+        let lower = DataSet::from_vecs(vec!(1.0, 5.0), vec!(0.0, 4.0), &opts).unwrap();
+
+        // only x = 1 to x = 4 is covered by both, and x = 2 is only in upper
+        // so lower should be interpolated there
+        let outline = FillBetween::between(&upper, &lower).outline();
+        assert_eq!(outline, vec!(
+            GraphCoord::new(1.0, 4.0), GraphCoord::new(2.0, 4.0), GraphCoord::new(4.0, 4.0),
+            GraphCoord::new(4.0, 3.0), GraphCoord::new(2.0, 1.0), GraphCoord::new(1.0, 0.0),
+        ));
+
+        // no overlap means nothing to fill
+        let apart = DataSet::from_vecs(vec!(10.0, 11.0), vec!(0.0, 0.0), &opts).unwrap();
+        assert_eq!(FillBetween::between(&upper, &apart).outline(), vec!());
+    }
+
+    #[test]
+    fn test_plot() {
+        let mut fake_canvas = MockCanvas::new();
+        let mut dims = GraphDimensions::new(600.0, 600.0);
+        dims.min = GraphCoord::new(0.0, 0.0);
+        dims.max = GraphCoord::new(2.0, 2.0);
+
+        let opts = DataSetOptions::default().fill_alpha(100);
+        let ds = DataSet::from_vecs(vec!(0.0, 1.0, 2.0), vec!(1.0, 2.0, 1.0), &opts).unwrap();
+
+        assert_eq!(FillBetween::new(&ds).plot(&dims, &mut fake_canvas), Ok(()));
+        // the opacity should always be reset once the fill is done
+        assert_eq!(fake_canvas.alpha, 255);
+    }
+}
@@ -0,0 +1,389 @@
+use options::{FinancialOptions, Orientation};
+use plottable::{Plottable, LegendEntry, Category};
+use plottable::primitives::{Bar, Line};
+use labeller::Labeller;
+use graph_dimensions::GraphDimensions;
+use canvas::Canvas;
+use pixel::{Color, GraphCoord};
+use std::f64;
+
+// The most x values written under a graph with a category axis
+const CATEGORY_LABELS: usize = 10;
+
+// The amount of ticks the `Labeller` aims for on the prices and on the volume
+// when they are labelled on their own scales
+const PRICE_TICKS: i32 = 5;
+const VOLUME_TICKS: i32 = 2;
+
+// How much of the volume panel the tallest volume bar takes up,
+// which leaves a gap between the volume and the lowest price
+const VOLUME_FILL: f64 = 0.9;
+
+/// `Ohlc` is the open, high, low and close value at a single x value
+/// as well as the volume traded
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Ohlc {
+    pub x: f64,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,
+}
+
+impl Ohlc {
+    pub fn new(x: f64, open: f64, high: f64, low: f64, close: f64) -> Ohlc {
+        Ohlc {
+            x: x,
+            open: open,
+            high: high,
+            low: low,
+            close: close,
+            volume: 0.0,
+        }
+    }
+
+    pub fn volume(mut self, volume: f64) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    pub fn is_up(&self) -> bool {
+        self.close >= self.open
+    }
+}
+
+// The layout shared by candlestick and OHLC charts
+struct Records<'a> {
+    records: &'a [Ohlc],
+    opts: &'a FinancialOptions<'a>,
+}
+
+impl <'a> Records<'a> {
+    // where each record is placed along the x axis
+    fn positions(&self) -> Vec<f64> {
+        if self.opts.category_axis {
+            (0..self.records.len()).map(|n| n as f64).collect()
+        } else {
+            self.records.iter().map(|r| r.x).collect()
+        }
+    }
+
+    // The width is based on the smallest gap between two records so that
+    // a gap in the data doesn't make the candles overlap or grow
+    fn width(&self) -> f64 {
+        let mut positions = self.positions();
+        positions.sort_by(|a, b| a.partial_cmp(b).expect("x values should not be NaN"));
+
+        let gap = positions.windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .filter(|&gap| gap > 0.0)
+            .fold(f64::MAX, |acc, gap| acc.min(gap));
+        let gap = if gap == f64::MAX { 1.0 } else { gap };
+
+        gap * self.opts.width
+    }
+
+    fn color(&self, record: &Ohlc) -> Color {
+        if record.is_up() { self.opts.up_color } else { self.opts.down_color }
+    }
+
+    fn price_range(&self) -> (f64, f64) {
+        let low = self.records.iter().fold(f64::MAX, |acc, r| acc.min(r.low));
+        let high = self.records.iter().fold(f64::MIN, |acc, r| acc.max(r.high));
+        (low, high)
+    }
+
+    fn max_volume(&self) -> f64 {
+        self.records.iter().fold(0.0, |acc: f64, r| acc.max(r.volume))
+    }
+
+    // The bottom and top of the volume panel, which goes under the lowest price
+    fn volume_panel(&self) -> Option<(f64, f64)> {
+        if self.opts.volume_panel <= 0.0 || self.records.is_empty() || self.max_volume() <= 0.0 {
+            return None;
+        }
+
+        let (low, high) = self.price_range();
+        let panel = self.opts.volume_panel.clamp(0.0, 0.9);
+        let height = (high - low) * panel / (1.0 - panel);
+        if height > 0.0 { Some((low - height, low)) } else { None }
+    }
+
+    // Where a volume is drawn up to inside of the volume panel
+    fn volume_height(&self, volume: f64, (bottom, top): (f64, f64)) -> f64 {
+        bottom + (top - bottom) * VOLUME_FILL * volume / self.max_volume()
+    }
+
+    fn extent(&self) -> Option<(GraphCoord, GraphCoord)> {
+        if self.records.is_empty() {
+            return None;
+        }
+
+        let half_width = self.width() / 2.0;
+        let positions = self.positions();
+
+        let min_x = positions.iter().fold(f64::MAX, |acc, &x| acc.min(x)) - half_width;
+        let max_x = positions.iter().fold(f64::MIN, |acc, &x| acc.max(x)) + half_width;
+        let (low, high) = self.price_range();
+        let low = self.volume_panel().map_or(low, |(bottom, _)| bottom);
+
+        Some((GraphCoord::new(min_x, low), GraphCoord::new(max_x, high)))
+    }
+
+    // The volume is drawn in its own panel under the prices with its own scale
+    // so the tallest volume bar takes up the whole panel
+    fn plot_volume<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let panel = match self.volume_panel() {
+            Some(panel) => panel,
+            None => return Ok(()),
+        };
+
+        let width = self.width();
+
+        canvas.set_alpha(128);
+        for (record, x) in self.records.iter().zip(self.positions()) {
+            canvas.set_color(self.color(record));
+
+            let res = Bar::new(x, panel.0, self.volume_height(record.volume, panel), width, Orientation::Vertical)
+                .plot(bounds, canvas);
+            if res.is_err() {
+                canvas.set_alpha(255);
+                return res;
+            }
+        }
+        canvas.set_alpha(255);
+
+        Ok(())
+    }
+
+    // On a category axis the x values of the records are lost, so the x value
+    // of every few records is written along the x axis instead
+    fn x_categories(&self) -> Vec<(f64, Category<'a>)> {
+        if !self.opts.category_axis || self.records.is_empty() {
+            return vec!();
+        }
+
+        let every = self.records.len().div_ceil(CATEGORY_LABELS);
+        self.records.iter().zip(self.positions()).step_by(every)
+            .map(|(record, x)| (x, Category::Value(record.x)))
+            .collect()
+    }
+
+    // With a volume panel the numbers on the y axis would be prices all the way down
+    // through the panel, so the prices and the volume are each labelled on their own scale
+    fn y_categories(&self) -> Vec<(f64, Category<'a>)> {
+        let panel = match self.volume_panel() {
+            Some(panel) => panel,
+            None => return vec!(),
+        };
+
+        let (low, high) = self.price_range();
+        let prices = Labeller::in_base10().search(low, high, PRICE_TICKS);
+        let volumes = Labeller::in_base10().search(0.0, self.max_volume(), VOLUME_TICKS);
+
+        let mut categories = Vec::new();
+        let mut price = (prices.min / prices.step).ceil() * prices.step;
+        while prices.step > 0.0 && price <= high {
+            if price >= low {
+                categories.push((price, Category::Value(price)));
+            }
+            price += prices.step;
+        }
+
+        let mut volume = volumes.step;
+        while volumes.step > 0.0 && volume <= self.max_volume() {
+            categories.push((self.volume_height(volume, panel), Category::Value(volume)));
+            volume += volumes.step;
+        }
+
+        categories
+    }
+
+    fn legend_entries(&self) -> Vec<LegendEntry<'a>> {
+        vec!(LegendEntry::new(self.opts.name, self.opts.up_color).split(self.opts.down_color))
+    }
+}
+
+/// `CandlestickSeries` draws a box from the open to the close value
+/// of each record with a line from its low to its high
+pub struct CandlestickSeries<'a>(Records<'a>);
+
+impl <'a> CandlestickSeries<'a> {
+    pub fn new(records: &'a [Ohlc], opts: &'a FinancialOptions<'a>) -> Self {
+        CandlestickSeries(Records { records: records, opts: opts })
+    }
+}
+
+impl <'a> Plottable for CandlestickSeries<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let records = &self.0;
+        records.plot_volume(bounds, canvas)?;
+
+        let width = records.width();
+        for (record, x) in records.records.iter().zip(records.positions()) {
+            canvas.set_color(records.color(record));
+
+            Line(GraphCoord::new(x, record.low), GraphCoord::new(x, record.high)).plot(bounds, canvas)?;
+
+            if record.open == record.close {
+                let half_width = width / 2.0;
+                Line(GraphCoord::new(x - half_width, record.open), GraphCoord::new(x + half_width, record.open))
+                    .plot(bounds, canvas)?;
+            } else {
+                Bar::new(x, record.open, record.close, width, Orientation::Vertical).plot(bounds, canvas)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn data_extent(&self) -> Option<(GraphCoord, GraphCoord)> {
+        self.0.extent()
+    }
+
+    fn legend_entries(&self) -> Vec<LegendEntry<'_>> {
+        self.0.legend_entries()
+    }
+
+    fn x_categories(&self) -> Vec<(f64, Category<'_>)> {
+        self.0.x_categories()
+    }
+
+    fn y_categories(&self) -> Vec<(f64, Category<'_>)> {
+        self.0.y_categories()
+    }
+}
+
+/// `OhlcSeries` draws a line from the low to the high value of each record
+/// with a tick to the left at the open value and a tick to the right at the close value
+pub struct OhlcSeries<'a>(Records<'a>);
+
+impl <'a> OhlcSeries<'a> {
+    pub fn new(records: &'a [Ohlc], opts: &'a FinancialOptions<'a>) -> Self {
+        OhlcSeries(Records { records: records, opts: opts })
+    }
+}
+
+impl <'a> Plottable for OhlcSeries<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let records = &self.0;
+        records.plot_volume(bounds, canvas)?;
+
+        let half_width = records.width() / 2.0;
+        for (record, x) in records.records.iter().zip(records.positions()) {
+            canvas.set_color(records.color(record));
+
+            Line(GraphCoord::new(x, record.low), GraphCoord::new(x, record.high)).plot(bounds, canvas)?;
+            Line(GraphCoord::new(x - half_width, record.open), GraphCoord::new(x, record.open)).plot(bounds, canvas)?;
+            Line(GraphCoord::new(x, record.close), GraphCoord::new(x + half_width, record.close)).plot(bounds, canvas)?;
+        }
+
+        Ok(())
+    }
+
+    fn data_extent(&self) -> Option<(GraphCoord, GraphCoord)> {
+        self.0.extent()
+    }
+
+    fn legend_entries(&self) -> Vec<LegendEntry<'_>> {
+        self.0.legend_entries()
+    }
+
+    fn x_categories(&self) -> Vec<(f64, Category<'_>)> {
+        self.0.x_categories()
+    }
+
+    fn y_categories(&self) -> Vec<(f64, Category<'_>)> {
+        self.0.y_categories()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::mock_canvas::MockCanvas;
+    use graph_dimensions::GraphDimensions;
+    use options::FinancialOptions;
+    use pixel::{Color, GraphCoord};
+    use plottable::Plottable;
+
+    fn records() -> Vec<Ohlc> {
+        vec!(
+            Ohlc::new(1.0, 10.0, 12.0, 9.0, 11.0).volume(100.0),
+            Ohlc::new(2.0, 11.0, 11.5, 8.0, 9.0).volume(50.0),
+            // a gap over the weekend
+            Ohlc::new(5.0, 9.0, 10.0, 9.0, 9.0).volume(10.0),
+        )
+    }
+
+    #[test]
+    fn test_extent() {
+        let records = records();
+        let opts = FinancialOptions::new().width(1.0);
+
+        // the gap shouldn't make the candles any wider than the smallest spacing
+        let candles = CandlestickSeries::new(&records, &opts);
+        assert_eq!(candles.data_extent(), Some((GraphCoord::new(0.5, 8.0), GraphCoord::new(5.5, 12.0))));
+
+        // on a category axis the records are placed one after another
+        let opts = FinancialOptions::new().width(1.0).category_axis(true);
+        let candles = OhlcSeries::new(&records, &opts);
+        assert_eq!(candles.data_extent(), Some((GraphCoord::new(-0.5, 8.0), GraphCoord::new(2.5, 12.0))));
+
+        // the volume panel should take up space below the lowest low
+        let opts = FinancialOptions::new().width(1.0).volume_panel(0.5);
+        let candles = CandlestickSeries::new(&records, &opts);
+        assert_eq!(candles.data_extent(), Some((GraphCoord::new(0.5, 4.0), GraphCoord::new(5.5, 12.0))));
+    }
+
+    #[test]
+    fn test_categories() {
+        let records = records();
+
+        // without a category axis or volume panel the axes keep their numbers
+        let opts = FinancialOptions::new();
+        let candles = CandlestickSeries::new(&records, &opts);
+        assert!(candles.x_categories().is_empty());
+        assert!(candles.y_categories().is_empty());
+
+        // the original x values are written at the positions of the records
+        let opts = FinancialOptions::new().category_axis(true);
+        let candles = CandlestickSeries::new(&records, &opts);
+        assert_eq!(candles.x_categories(), vec!(
+            (0.0, Category::Value(1.0)), (1.0, Category::Value(2.0)), (2.0, Category::Value(5.0)),
+        ));
+
+        // the prices are only labelled above the panel, and the volume inside of it
+        let opts = FinancialOptions::new().volume_panel(0.5);
+        let candles = CandlestickSeries::new(&records, &opts);
+        let categories = candles.y_categories();
+        let prices = categories.iter().filter(|c| c.0 >= 8.0).collect::<Vec<_>>();
+        let volumes = categories.iter().filter(|c| c.0 < 8.0).collect::<Vec<_>>();
+
+        assert!(!prices.is_empty() && prices.iter().all(|&&(y, c)| c == Category::Value(y) && y <= 12.0));
+        assert!(!volumes.is_empty() && volumes.iter().all(|&&(y, c)| match c {
+            Category::Value(v) => v > 0.0 && v <= 100.0 && y == 4.0 + 4.0 * 0.9 * v / 100.0,
+            _ => false,
+        }));
+    }
+
+    #[test]
+    fn test_plot() {
+        let mut fake_canvas = MockCanvas::new();
+        let records = records();
+        let opts = FinancialOptions::new().volume_panel(0.2).name("ACME");
+
+        let candles = CandlestickSeries::new(&records, &opts);
+        let (min, max) = candles.data_extent().unwrap();
+
+        let mut dims = GraphDimensions::new(600.0, 600.0);
+        dims.min = min;
+        dims.max = max;
+
+        assert_eq!(candles.plot(&dims, &mut fake_canvas), Ok(()));
+        assert_eq!(OhlcSeries::new(&records, &opts).plot(&dims, &mut fake_canvas), Ok(()));
+        assert_eq!(fake_canvas.alpha, 255);
+        assert_eq!(candles.legend_entries(), vec!(LegendEntry::new("ACME", opts.up_color).split(opts.down_color)));
+    }
+}
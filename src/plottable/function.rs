@@ -0,0 +1,107 @@
+use data_set::DataSet;
+use options::DataSetOptions;
+use plottable::{Plottable, LegendEntry};
+use plottable::graphs::LineSeries;
+use graph_dimensions::GraphDimensions;
+use canvas::Canvas;
+use pixel::GraphCoord;
+use sampler;
+
+/// `FunctionSeries` draws y = f(x) as a line, the function is sampled again
+/// every time it is drawn so the line stays smooth as the view is panned or zoomed
+///
+/// Samples are taken wherever the curve bends or changes quickly and no
+/// line is drawn across a jump or asymptote such as those of tan
+pub struct FunctionSeries<'a, F> {
+    f: F,
+    opts: &'a DataSetOptions<'a>,
+    range: Option<(f64, f64)>,
+}
+
+impl <'a, F> FunctionSeries<'a, F> where F: Fn(f64) -> f64 {
+    pub fn new(opts: &'a DataSetOptions<'a>, f: F) -> Self {
+        FunctionSeries {
+            f: f,
+            opts: opts,
+            range: None,
+        }
+    }
+
+    /// Only draws the function between `x_min` and `x_max`, without a range
+    /// the function is drawn across the whole view
+    pub fn range(mut self, x_min: f64, x_max: f64) -> Self {
+        self.range = Some((x_min, x_max));
+        self
+    }
+
+    /// The samples that would be drawn on `bounds`
+    pub fn samples(&self, bounds: &GraphDimensions) -> Vec<GraphCoord> {
+        let (x_min, x_max) = match self.range {
+            Some((x_min, x_max)) => (x_min.max(bounds.min.x), x_max.min(bounds.max.x)),
+            None => (bounds.min.x, bounds.max.x),
+        };
+
+        sampler::sample(&self.f, x_min, x_max, bounds)
+    }
+}
+
+impl <'a, F> Plottable for FunctionSeries<'a, F> where F: Fn(f64) -> f64 {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let ds = DataSet::new(self.samples(bounds), self.opts);
+        LineSeries(&ds).plot(bounds, canvas)
+    }
+
+    /// The range along with most of the values of the function, values near an
+    /// asymptote are left out so they don't squash the rest of the curve
+    fn data_extent(&self) -> Option<(GraphCoord, GraphCoord)> {
+        let (x_min, x_max) = self.range?;
+        let bounds = sampler::default_bounds(&self.f, x_min, x_max);
+
+        if bounds.min.y > bounds.max.y {
+            return None;
+        }
+        Some((bounds.min, bounds.max))
+    }
+
+    fn legend_entries(&self) -> Vec<LegendEntry<'_>> {
+        vec!(LegendEntry::new(self.opts.name, self.opts.color))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::mock_canvas::MockCanvas;
+    use graph_dimensions::GraphDimensions;
+    use options::DataSetOptions;
+    use pixel::GraphCoord;
+    use plottable::Plottable;
+
+    #[test]
+    fn test_resamples_for_view() {
+        let opts = DataSetOptions::default();
+        let series = FunctionSeries::new(&opts, |x: f64| x.sin()).range(0.0, 10.0);
+
+        let (min, max) = series.data_extent().unwrap();
+        assert_eq!((min.x, max.x), (0.0, 10.0));
+
+        let mut dims = GraphDimensions::new(600.0, 600.0);
+        dims.min = min;
+        dims.max = max;
+        let whole = series.samples(&dims);
+        assert!(whole.iter().all(|p| p.x >= 0.0 && p.x <= 10.0));
+
+        // after zooming in the samples should only cover the view, and be closer together
+        dims.min = GraphCoord::new(2.0, -1.0);
+        dims.max = GraphCoord::new(3.0, 1.0);
+        let zoomed = series.samples(&dims);
+        assert_eq!((zoomed[0].x, zoomed[zoomed.len() - 1].x), (2.0, 3.0));
+        assert!(zoomed[1].x - zoomed[0].x < whole[1].x - whole[0].x);
+
+        let mut fake_canvas = MockCanvas::new();
+        assert_eq!(series.plot(&dims, &mut fake_canvas), Ok(()));
+
+        // without a range there's nothing to fit the view to
+        assert_eq!(FunctionSeries::new(&opts, |x: f64| x.tan()).data_extent(), None);
+    }
+}
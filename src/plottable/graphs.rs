@@ -1,21 +1,30 @@
 use data_set::DataSet;
 use plottable::primitives::*;
-use plottable::{Plottable, HasDataSet};
+use plottable::{Plottable, HasDataSet, FillBetween};
 use graph_dimensions::GraphDimensions;
 use canvas::Canvas;
+use options::{DataSetOptions, PlotStyle, Orientation, ErrorBarStyle, StepMode, SizeScale};
+use pixel::{Color, GraphCoord};
 
 pub struct LineSeries<'a>(pub &'a DataSet<'a>);
 
-impl <'a> Plottable for LineSeries<'a> {
-    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+impl <'a> LineSeries<'a> {
+    /// Draws the line in `color` rather than choosing one from the options
+    pub fn plot_with_color<C: Canvas>(&self, color: Color, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
         let ds = self.0;
-        canvas.set_color(ds.choose_color());
+        canvas.set_color(color);
         
         for pair in ds.data_points.windows(2) {
             Line(pair[0], pair[1]).plot(bounds, canvas)?;
         }
 
-        Ok(())
+        ErrorBars(ds).plot(bounds, canvas)
+    }
+}
+
+impl <'a> Plottable for LineSeries<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        self.plot_with_color(self.0.choose_color(), bounds, canvas)
     }
 }
 
@@ -36,7 +45,8 @@ impl <'a> Plottable for ScatterSeries<'a> {
             Point(point, ds.options.point_style).plot(bounds, canvas)?;
         } 
 
-        Ok(())
+        canvas.set_color(ds.choose_color());
+        ErrorBars(ds).plot(bounds, canvas)
     }
 }
 
@@ -52,11 +62,384 @@ impl <'a> Plottable for BarSeries<'a> {
     fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
         let ds = self.0;
 
+        let opts = ds.options;
+
+        for point in ds.data_points.iter() {
+            canvas.set_color(ds.choose_color());
+            Bar::new(point.x, opts.baseline, point.y, opts.bar_width, opts.orientation).plot(bounds, canvas)?;
+        }
+
+        ErrorBars(ds).plot(bounds, canvas)
+    }
+}
+
+impl <'a> HasDataSet for BarSeries<'a> {
+    fn data_set(&self) -> &DataSet {
+        self.0
+    }
+}
+
+/// `AreaSeries` is a line graph with the region between the line
+/// and the baseline in the `DataSetOptions` filled in
+pub struct AreaSeries<'a>(pub &'a DataSet<'a>);
+
+impl <'a> Plottable for AreaSeries<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let ds = self.0;
+
+        // a random colour is only chosen once so the fill matches its line
+        let color = ds.choose_color();
+        FillBetween::new(ds).color(ds.options.fill_color.unwrap_or(color)).plot(bounds, canvas)?;
+        LineSeries(ds).plot_with_color(color, bounds, canvas)
+    }
+}
+
+impl <'a> HasDataSet for AreaSeries<'a> {
+    fn data_set(&self) -> &DataSet {
+        self.0
+    }
+}
+
+/// `StepSeries` joins each point to the next with a horizontal
+/// and a vertical line, where the vertical line goes depends on the `StepMode`
+pub struct StepSeries<'a>(pub &'a DataSet<'a>, pub StepMode);
+
+impl <'a> StepSeries<'a> {
+    /// The corners of the steps between each point
+    pub fn step_points(&self) -> Vec<GraphCoord> {
+        let points = &self.0.data_points;
+        let mut steps = Vec::with_capacity(points.len() * 2);
+
+        if let Some(&first) = points.first() {
+            steps.push(first);
+        }
+
+        for pair in points.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            match self.1 {
+                StepMode::Pre => steps.push(GraphCoord::new(a.x, b.y)),
+                StepMode::Post => steps.push(GraphCoord::new(b.x, a.y)),
+                StepMode::Mid => {
+                    let mid = (a.x + b.x) / 2.0;
+                    steps.push(GraphCoord::new(mid, a.y));
+                    steps.push(GraphCoord::new(mid, b.y));
+                },
+            }
+            steps.push(b);
+        }
+
+        steps
+    }
+}
+
+impl <'a> Plottable for StepSeries<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let ds = self.0;
+        canvas.set_color(ds.choose_color());
+
+        for pair in self.step_points().windows(2) {
+            Line(pair[0], pair[1]).plot(bounds, canvas)?;
+        }
+
+        ErrorBars(ds).plot(bounds, canvas)
+    }
+}
+
+impl <'a> HasDataSet for StepSeries<'a> {
+    fn data_set(&self) -> &DataSet {
+        self.0
+    }
+}
+
+/// `StemSeries` draws a vertical line from the baseline to each point
+/// with the marker given by the point style on top, the baseline itself
+/// is drawn in the baseline colour
+pub struct StemSeries<'a>(pub &'a DataSet<'a>);
+
+impl <'a> Plottable for StemSeries<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let ds = self.0;
+        let baseline = ds.options.baseline;
+
+        let (min, max) = (ds.get_min_coord(), ds.get_max_coord());
+        canvas.set_color(ds.options.baseline_color);
+        Line(GraphCoord::new(min.x, baseline), GraphCoord::new(max.x, baseline)).plot(bounds, canvas)?;
+
         for &point in ds.data_points.iter() {
             canvas.set_color(ds.choose_color());
-            Bar(point).plot(bounds, canvas)?;
+            Line(GraphCoord::new(point.x, baseline), point).plot(bounds, canvas)?;
+            Point(point, ds.options.point_style).plot(bounds, canvas)?;
+        }
+
+        canvas.set_color(ds.choose_color());
+        ErrorBars(ds).plot(bounds, canvas)
+    }
+}
+
+impl <'a> HasDataSet for StemSeries<'a> {
+    fn data_set(&self) -> &DataSet {
+        self.0
+    }
+}
+
+/// `BubbleSeries` draws a filled circle at each point with its radius
+/// set by the size of the point, see `DataSet::sizes`
+pub struct BubbleSeries<'a>(pub &'a DataSet<'a>);
+
+impl <'a> BubbleSeries<'a> {
+    /// The radius in pixels of a bubble for `size` when the largest
+    /// size in the graph is `max_size`
+    pub fn radius_for(opts: &DataSetOptions, size: f64, max_size: f64) -> f64 {
+        let (min_radius, max_radius) = opts.bubble_radius;
+        if max_size <= 0.0 || max_size.is_nan() || !size.is_finite() {
+            return min_radius;
+        }
+
+        let fraction = (size / max_size).clamp(0.0, 1.0);
+        match opts.size_scale {
+            SizeScale::Linear => min_radius + fraction * (max_radius - min_radius),
+            // the area is only proportional to the size if the radius starts from zero
+            SizeScale::Sqrt => (max_radius * fraction.sqrt()).max(min_radius),
+        }
+    }
+}
+
+impl <'a> Plottable for BubbleSeries<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let ds = self.0;
+        let max_size = ds.get_max_size();
+
+        let mut bubbles = ds.data_points.iter().enumerate().map(|(n, &point)| {
+            let size = ds.sizes.as_ref().and_then(|sizes| sizes.get(n)).cloned().unwrap_or(0.0);
+            (point, BubbleSeries::radius_for(ds.options, size, max_size))
+        }).collect::<Vec<_>>();
+
+        // draw the biggest bubbles first so the small ones aren't hidden
+        bubbles.sort_by(|a, b| b.1.partial_cmp(&a.1).expect("radius should not be NaN"));
+
+        for &(point, radius) in bubbles.iter() {
+            let pix = match bounds.convert_to_pixel(point) {
+                Some(pix) => pix,
+                None => continue,
+            };
+            let outline = Circle::outline(pix, radius);
+
+            canvas.set_color(ds.options.fill_color.unwrap_or(ds.choose_color()));
+            canvas.set_alpha(ds.options.fill_alpha);
+            let res = canvas.fill_polygon(&outline);
+            canvas.set_alpha(255);
+            res?;
+
+            canvas.set_color(ds.choose_color());
+            for n in 0..outline.len() {
+                canvas.draw_line(outline[n], outline[(n + 1) % outline.len()])?;
+            }
+        }
+
+        ErrorBars(ds).plot(bounds, canvas)
+    }
+}
+
+impl <'a> HasDataSet for BubbleSeries<'a> {
+    fn data_set(&self) -> &DataSet {
+        self.0
+    }
+}
+
+/// `ErrorBars` draws the x and y error bars of a `DataSet` in the active colour
+/// nothing is drawn if the `DataSet` doesn't have any error bars
+pub struct ErrorBars<'a>(pub &'a DataSet<'a>);
+
+impl <'a> ErrorBars<'a> {
+    // `orientation` is the direction the error bar runs in, the caps go across it
+    fn plot_error_bar<C: Canvas>(&self, start: GraphCoord, end: GraphCoord, orientation: Orientation,
+        bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+
+        Line(start, end).plot(bounds, canvas)?;
+
+        if self.0.options.error_style == ErrorBarStyle::Plain {
+            return Ok(());
+        }
+
+        let half_cap = self.0.options.error_cap_width / 2.0;
+        for &end in &[start, end] {
+            if let Some(pix) = bounds.convert_to_pixel(end) {
+                if orientation == Orientation::Vertical {
+                    canvas.draw_line((pix.x - half_cap, pix.y), (pix.x + half_cap, pix.y))?;
+                } else {
+                    canvas.draw_line((pix.x, pix.y - half_cap), (pix.x, pix.y + half_cap))?;
+                }
+            }
         }
 
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+impl <'a> Plottable for ErrorBars<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let ds = self.0;
+        let opts = ds.options;
+
+        // the points of a horizontal bar graph are drawn with x and y swapped
+        let swap = opts.plot_style == PlotStyle::Bar && opts.orientation == Orientation::Horizontal;
+        let place = |p: GraphCoord| if swap { GraphCoord::new(p.y, p.x) } else { p };
+        let (x_orientation, y_orientation) = if swap {
+            (Orientation::Vertical, Orientation::Horizontal)
+        } else {
+            (Orientation::Horizontal, Orientation::Vertical)
+        };
+
+        for (n, &p) in ds.data_points.iter().enumerate() {
+            if let Some(e) = ds.x_errors.as_ref().and_then(|errors| errors.get(n)) {
+                let start = place(GraphCoord::new(p.x - e.minus, p.y));
+                let end = place(GraphCoord::new(p.x + e.plus, p.y));
+                self.plot_error_bar(start, end, x_orientation, bounds, canvas)?;
+            }
+            if let Some(e) = ds.y_errors.as_ref().and_then(|errors| errors.get(n)) {
+                let start = place(GraphCoord::new(p.x, p.y - e.minus));
+                let end = place(GraphCoord::new(p.x, p.y + e.plus));
+                self.plot_error_bar(start, end, y_orientation, bounds, canvas)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl <'a> HasDataSet for ErrorBars<'a> {
+    fn data_set(&self) -> &DataSet {
+        self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_set::{DataSet, ErrorBar};
+    use options::{DataSetOptions, StepMode, SizeScale, PointStyle};
+    use canvas::mock_canvas::MockCanvas;
+    use graph_dimensions::GraphDimensions;
+    use pixel::{Color, GraphCoord, Pixel};
+
+    #[test]
+    fn test_radius_for() {
+        let opts = DataSetOptions::default().bubble_radius(0.0, 10.0);
+
+        // with a square root scale a quarter of the size should be half the radius
+        assert_eq!(BubbleSeries::radius_for(&opts, 25.0, 100.0), 5.0);
+        assert_eq!(BubbleSeries::radius_for(&opts, 100.0, 100.0), 10.0);
+
+        let opts = opts.size_scale(SizeScale::Linear);
+        assert_eq!(BubbleSeries::radius_for(&opts, 25.0, 100.0), 2.5);
+
+        // anything without a sensible size gets the smallest radius
+        assert_eq!(BubbleSeries::radius_for(&opts, 25.0, 0.0), 0.0);
+
+        // the smallest radius doesn't stop the area being proportional to the size
+        let opts = DataSetOptions::default().bubble_radius(3.0, 30.0);
+        assert_eq!(BubbleSeries::radius_for(&opts, 25.0, 100.0), 15.0);
+        assert_eq!(BubbleSeries::radius_for(&opts, 0.1, 100.0), 3.0);
+    }
+
+    #[test]
+    fn test_area_series() {
+        let mut fake_canvas = MockCanvas::new();
+        let colors = [Color(255, 0, 0), Color(0, 255, 0), Color(0, 0, 255), Color(255, 255, 0),
+                      Color(255, 0, 255), Color(0, 255, 255), Color(128, 0, 0), Color(0, 128, 0)];
+        let opts = DataSetOptions::default().random_color(true).colors(&colors[..]);
+        let ds = DataSet::from_vecs(vec!(0.0, 1.0, 2.0), vec!(1.0, 3.0, 2.0), &opts).unwrap();
+
+        let mut dims = GraphDimensions::new(600.0, 600.0);
+        dims.min = GraphCoord::new(0.0, 0.0);
+        dims.max = GraphCoord::new(2.0, 3.0);
+
+        // the fill and the line should both be in the one colour that was chosen
+        assert_eq!(AreaSeries(&ds).plot(&dims, &mut fake_canvas), Ok(()));
+        let color = fake_canvas.lines[0].2;
+        assert!(colors.contains(&color));
+        assert!(fake_canvas.lines.iter().all(|&(_, _, c)| c == color));
+    }
+
+    #[test]
+    fn test_error_bar_caps() {
+        let mut fake_canvas = MockCanvas::new();
+        let opts = DataSetOptions::default().error_cap_width(10.0);
+        let ds = DataSet::from_vecs(vec!(1.0), vec!(1.0), &opts).unwrap()
+            .x_errors(vec!(ErrorBar::symmetric(0.0))).unwrap();
+
+        let mut dims = GraphDimensions::new(600.0, 600.0);
+        dims.min = GraphCoord::new(0.0, 0.0);
+        dims.max = GraphCoord::new(2.0, 2.0);
+
+        // an x error bar with no length still gets vertical caps
+        assert_eq!(ErrorBars(&ds).plot(&dims, &mut fake_canvas), Ok(()));
+        let caps = &fake_canvas.lines[1..];
+        assert_eq!(caps.len(), 2);
+        assert!(caps.iter().all(|&(start, end, _)| start.x == end.x && (end.y - start.y).abs() == 10.0));
+    }
+
+    #[test]
+    fn test_step_points() {
+        let opts = DataSetOptions::default();
+        let ds = DataSet::from_vecs(vec!(0.0, 2.0, 4.0), vec!(1.0, 3.0, 2.0), &opts).unwrap();
+
+        let pre = StepSeries(&ds, StepMode::Pre).step_points();
+        assert_eq!(pre, vec!(
+            GraphCoord::new(0.0, 1.0), GraphCoord::new(0.0, 3.0), GraphCoord::new(2.0, 3.0),
+            GraphCoord::new(2.0, 2.0), GraphCoord::new(4.0, 2.0),
+        ));
+
+        let post = StepSeries(&ds, StepMode::Post).step_points();
+        assert_eq!(post, vec!(
+            GraphCoord::new(0.0, 1.0), GraphCoord::new(2.0, 1.0), GraphCoord::new(2.0, 3.0),
+            GraphCoord::new(4.0, 3.0), GraphCoord::new(4.0, 2.0),
+        ));
+
+        let mid = StepSeries(&ds, StepMode::Mid).step_points();
+        assert_eq!(mid, vec!(
+            GraphCoord::new(0.0, 1.0), GraphCoord::new(1.0, 1.0), GraphCoord::new(1.0, 3.0),
+            GraphCoord::new(2.0, 3.0), GraphCoord::new(3.0, 3.0), GraphCoord::new(3.0, 2.0),
+            GraphCoord::new(4.0, 2.0),
+        ));
+    }
+
+    #[test]
+    fn test_stem_series() {
+        let mut fake_canvas = MockCanvas::new();
+        let opts = DataSetOptions::default()
+            .color(Color(0, 0, 255))
+            .baseline(2.0)
+            .baseline_color(Color(255, 0, 0))
+            .point_style(PointStyle::Circle);
+        let ds = DataSet::from_vecs(vec!(0.0, 1.0, 2.0), vec!(5.0, -1.0, 3.0), &opts).unwrap();
+
+        let mut dims = GraphDimensions::new(600.0, 600.0);
+        dims.min = GraphCoord::new(0.0, -1.0);
+        dims.max = GraphCoord::new(2.0, 5.0);
+
+        assert_eq!(StemSeries(&ds).plot(&dims, &mut fake_canvas), Ok(()));
+        let baseline = dims.convert_to_pixel((0.0, 2.0)).unwrap().y;
+
+        // the baseline runs under every point in its own colour
+        let (start, end, color) = fake_canvas.lines[0];
+        assert_eq!((start.y, end.y, color), (baseline, baseline, Color(255, 0, 0)));
+        assert_eq!((start.x, end.x), (dims.convert_to_pixel((0.0, 2.0)).unwrap().x, dims.convert_to_pixel((2.0, 2.0)).unwrap().x));
+
+        // each stem starts on the baseline, rather than at zero, and goes up or down to its point
+        for point in ds.data_points.iter() {
+            let top = dims.convert_to_pixel(*point).unwrap();
+            assert!(fake_canvas.lines.iter().any(|&(start, end, color)| {
+                start == Pixel::new(top.x, baseline) && end == top && color == Color(0, 0, 255)
+            }));
+        }
+
+        // a circle marker is a closed ring of 12 lines around each point
+        let stems = ds.data_points.len();
+        assert_eq!(fake_canvas.lines.len(), 1 + stems * (1 + 12));
+        let top = dims.convert_to_pixel(ds.data_points[0]).unwrap();
+        let ring = &fake_canvas.lines[2..14];
+        assert!(ring.iter().all(|&(start, _, _)| ((start.x - top.x).hypot(start.y - top.y) - 3.5).abs() < 1e-9));
+        assert_eq!(ring[0].0, ring[11].1);
+    }
+}
@@ -0,0 +1,247 @@
+use grid::Grid;
+use colormap::Colormap;
+use pixel::{Color, GraphCoord3D};
+use std::f64;
+
+/// A `Shape3D` is a single thing drawn by a `Graph3D`, every shape is
+/// projected and then drawn from the furthest away to the nearest
+#[derive(Clone, PartialEq, Debug)]
+pub enum Shape3D {
+    /// A filled polygon, with an optional outline
+    Polygon(Vec<GraphCoord3D>, Color, Option<Color>),
+    Line(GraphCoord3D, GraphCoord3D, Color),
+    Point(GraphCoord3D, Color),
+}
+
+impl Shape3D {
+    pub fn points(&self) -> Vec<GraphCoord3D> {
+        match *self {
+            Shape3D::Polygon(ref points, ..) => points.clone(),
+            Shape3D::Line(a, b, _) => vec!(a, b),
+            Shape3D::Point(p, _) => vec!(p),
+        }
+    }
+}
+
+/// `Plottable3D` is anything that can be drawn on a `Graph3D`
+///
+/// Unlike `Plottable` a series doesn't draw itself, it returns the shapes
+/// to draw so `Graph3D` can sort every shape by depth before drawing them
+pub trait Plottable3D {
+    fn shapes(&self) -> Vec<Shape3D>;
+
+    /// The minimum and maximum `GraphCoord3D` that this will draw
+    fn extent(&self) -> Option<(GraphCoord3D, GraphCoord3D)> {
+        extent_of(self.shapes().iter().flat_map(|s| s.points()))
+    }
+}
+
+fn extent_of<I: Iterator<Item=GraphCoord3D>>(points: I) -> Option<(GraphCoord3D, GraphCoord3D)> {
+    let mut min = GraphCoord3D::new(f64::MAX, f64::MAX, f64::MAX);
+    let mut max = GraphCoord3D::new(f64::MIN, f64::MIN, f64::MIN);
+    let mut any = false;
+
+    for p in points.filter(|p| p.x.is_finite() && p.y.is_finite() && p.z.is_finite()) {
+        min = GraphCoord3D::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z));
+        max = GraphCoord3D::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z));
+        any = true;
+    }
+
+    if any { Some((min, max)) } else { None }
+}
+
+// The point of the grid at column i and row j
+fn grid_point(grid: &Grid, i: usize, j: usize) -> GraphCoord3D {
+    GraphCoord3D::new(grid.x[i], grid.y[j], grid.z[j][i])
+}
+
+/// `SurfaceSeries` draws a `Grid` as a surface, each cell of the grid is
+/// filled with the colour of its average height from the colormap
+pub struct SurfaceSeries<'a> {
+    grid: &'a Grid,
+    colormap: Colormap,
+    edges: Option<Color>,
+}
+
+impl <'a> SurfaceSeries<'a> {
+    pub fn new(grid: &'a Grid) -> Self {
+        SurfaceSeries {
+            grid: grid,
+            colormap: Colormap::default(),
+            edges: Some(Color(0, 0, 0)),
+        }
+    }
+
+    pub fn colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = colormap;
+        self
+    }
+
+    /// The colour of the outline of each cell, `None` leaves them out
+    pub fn edges<C: Into<Option<Color>>>(mut self, color: C) -> Self {
+        self.edges = color.into();
+        self
+    }
+}
+
+impl <'a> Plottable3D for SurfaceSeries<'a> {
+    fn shapes(&self) -> Vec<Shape3D> {
+        let grid = self.grid;
+        let (min, max) = (grid.min_value(), grid.max_value());
+        let mut shapes = Vec::new();
+
+        for j in 0..grid.y.len().saturating_sub(1) {
+            for i in 0..grid.x.len().saturating_sub(1) {
+                let corners = vec!(grid_point(grid, i, j), grid_point(grid, i + 1, j),
+                                   grid_point(grid, i + 1, j + 1), grid_point(grid, i, j + 1));
+
+                // a cell with a hole in it is left out rather than drawn wrong
+                if corners.iter().any(|c| !c.z.is_finite()) {
+                    continue;
+                }
+
+                let average = corners.iter().map(|c| c.z).sum::<f64>() / 4.0;
+                shapes.push(Shape3D::Polygon(corners, self.colormap.color_for(average, min, max), self.edges));
+            }
+        }
+
+        shapes
+    }
+}
+
+/// `WireframeSeries` draws the lines between each point of a `Grid`
+pub struct WireframeSeries<'a> {
+    grid: &'a Grid,
+    color: Color,
+}
+
+impl <'a> WireframeSeries<'a> {
+    pub fn new(grid: &'a Grid) -> Self {
+        WireframeSeries {
+            grid: grid,
+            color: Color(0, 0, 0),
+        }
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+impl <'a> Plottable3D for WireframeSeries<'a> {
+    fn shapes(&self) -> Vec<Shape3D> {
+        let grid = self.grid;
+        let mut shapes = Vec::new();
+
+        for j in 0..grid.y.len() {
+            for i in 0..grid.x.len() {
+                let p = grid_point(grid, i, j);
+                if i + 1 < grid.x.len() {
+                    shapes.push(Shape3D::Line(p, grid_point(grid, i + 1, j), self.color));
+                }
+                if j + 1 < grid.y.len() {
+                    shapes.push(Shape3D::Line(p, grid_point(grid, i, j + 1), self.color));
+                }
+            }
+        }
+
+        shapes.retain(|s| s.points().iter().all(|p| p.z.is_finite()));
+        shapes
+    }
+}
+
+/// `Line3DSeries` draws a line through each point in order
+pub struct Line3DSeries<'a> {
+    points: &'a [GraphCoord3D],
+    color: Color,
+}
+
+impl <'a> Line3DSeries<'a> {
+    pub fn new(points: &'a [GraphCoord3D]) -> Self {
+        Line3DSeries {
+            points: points,
+            color: Color(0, 0, 255),
+        }
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+impl <'a> Plottable3D for Line3DSeries<'a> {
+    fn shapes(&self) -> Vec<Shape3D> {
+        self.points.windows(2)
+            .map(|pair| Shape3D::Line(pair[0], pair[1], self.color))
+            .collect()
+    }
+
+    fn extent(&self) -> Option<(GraphCoord3D, GraphCoord3D)> {
+        extent_of(self.points.iter().cloned())
+    }
+}
+
+/// `Scatter3DSeries` draws a marker at each point
+pub struct Scatter3DSeries<'a> {
+    points: &'a [GraphCoord3D],
+    color: Color,
+}
+
+impl <'a> Scatter3DSeries<'a> {
+    pub fn new(points: &'a [GraphCoord3D]) -> Self {
+        Scatter3DSeries {
+            points: points,
+            color: Color(0, 0, 255),
+        }
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+impl <'a> Plottable3D for Scatter3DSeries<'a> {
+    fn shapes(&self) -> Vec<Shape3D> {
+        self.points.iter().map(|&p| Shape3D::Point(p, self.color)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grid::Grid;
+    use pixel::{Color, GraphCoord3D};
+
+    #[test]
+    fn test_grid_series() {
+        let grid = Grid::from_fn(vec!(0.0, 1.0, 2.0), vec!(0.0, 1.0), |x, y| x + y);
+
+        // one quad for each cell, coloured by the average height
+        let surface = SurfaceSeries::new(&grid).edges(None);
+        let shapes = surface.shapes();
+        assert_eq!(shapes.len(), 2);
+        match shapes[0] {
+            Shape3D::Polygon(ref corners, _, None) => assert_eq!(corners[2], GraphCoord3D::new(1.0, 1.0, 2.0)),
+            ref s => panic!("expected a polygon, got {:?}", s),
+        }
+        assert_eq!(surface.extent(), Some((GraphCoord3D::new(0.0, 0.0, 0.0), GraphCoord3D::new(2.0, 1.0, 3.0))));
+
+        // 2 lines along each of the 2 rows and 3 lines across
+        assert_eq!(WireframeSeries::new(&grid).shapes().len(), 7);
+    }
+
+    #[test]
+    fn test_point_series() {
+        let points = [GraphCoord3D::new(0.0, 0.0, 0.0), GraphCoord3D::new(1.0, 2.0, 3.0), GraphCoord3D::new(-1.0, 0.5, 1.0)];
+
+        let line = Line3DSeries::new(&points).color(Color(255, 0, 0));
+        assert_eq!(line.shapes()[1], Shape3D::Line(points[1], points[2], Color(255, 0, 0)));
+        assert_eq!(line.extent(), Some((GraphCoord3D::new(-1.0, 0.0, 0.0), GraphCoord3D::new(1.0, 2.0, 3.0))));
+
+        assert_eq!(Scatter3DSeries::new(&points).shapes().len(), 3);
+        assert_eq!(Scatter3DSeries::new(&[]).extent(), None);
+    }
+}
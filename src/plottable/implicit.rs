@@ -0,0 +1,215 @@
+use options::DataSetOptions;
+use plottable::{Plottable, LegendEntry};
+use plottable::primitives::Line;
+use marching_squares;
+use graph_dimensions::GraphDimensions;
+use canvas::Canvas;
+use pixel::GraphCoord;
+
+/// `ImplicitCurve` draws every point where f(x, y) = 0, such as
+/// x^2 + y^2 - 1 = 0 for a circle, the curve is found again every time it is
+/// drawn so it always covers the current view at the same level of detail
+///
+/// The view is split into a coarse grid of cells, cells the curve passes through
+/// are split into quarters until they are about a pixel across
+pub struct ImplicitCurve<'a, F> {
+    f: F,
+    opts: &'a DataSetOptions<'a>,
+    region: Option<(GraphCoord, GraphCoord)>,
+    cell_size: f64,
+    max_depth: u32,
+}
+
+impl <'a, F> ImplicitCurve<'a, F> where F: Fn(f64, f64) -> f64 {
+    pub fn new(opts: &'a DataSetOptions<'a>, f: F) -> Self {
+        ImplicitCurve {
+            f: f,
+            opts: opts,
+            region: None,
+            cell_size: 16.0,
+            max_depth: 4,
+        }
+    }
+
+    /// Only looks for the curve between `min` and `max`, without a region
+    /// the whole view is searched
+    pub fn region(mut self, min: GraphCoord, max: GraphCoord) -> Self {
+        self.region = Some((min, max));
+        self
+    }
+
+    /// The size in pixels of the cells of the coarse grid, loops of the curve
+    /// much smaller than a cell may be missed
+    pub fn cell_size(mut self, cell_size: f64) -> Self {
+        self.cell_size = cell_size.max(1.0);
+        self
+    }
+
+    /// How many times a cell the curve passes through can be split into quarters
+    pub fn max_depth(mut self, max_depth: u32) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// The line segments making up the curve when it is drawn on `bounds`
+    pub fn segments(&self, bounds: &GraphDimensions) -> Vec<Line> {
+        let (mut min, mut max) = (bounds.min, bounds.max);
+        if let Some((region_min, region_max)) = self.region {
+            min = GraphCoord::new(min.x.max(region_min.x), min.y.max(region_min.y));
+            max = GraphCoord::new(max.x.min(region_max.x), max.y.min(region_max.y));
+        }
+
+        let mut lines = Vec::new();
+        if !(max.x > min.x && max.y > min.y) {
+            return lines;
+        }
+
+        let (pixel_width, pixel_height) = bounds.pixel_size();
+        let columns = ((max.x - min.x) / (pixel_width * self.cell_size)).ceil().max(1.0) as usize;
+        let rows = ((max.y - min.y) / (pixel_height * self.cell_size)).ceil().max(1.0) as usize;
+        let (width, height) = ((max.x - min.x) / (columns as f64), (max.y - min.y) / (rows as f64));
+
+        // each value is only worked out once for the coarse grid
+        let values = (0..(rows + 1)).map(|j| {
+            (0..(columns + 1)).map(|i| self.at(min.x + width * (i as f64), min.y + height * (j as f64))).collect::<Vec<_>>()
+        }).collect::<Vec<_>>();
+
+        for j in 0..rows {
+            for i in 0..columns {
+                let corners = [values[j][i], values[j][i + 1], values[j + 1][i + 1], values[j + 1][i]];
+                self.refine(corners, 0, &mut lines);
+            }
+        }
+
+        lines
+    }
+
+    fn at(&self, x: f64, y: f64) -> (GraphCoord, f64) {
+        (GraphCoord::new(x, y), (self.f)(x, y))
+    }
+
+    // Splits a cell into quarters until it is small enough to draw, the corners
+    // are anticlockwise starting at the bottom left like `cell_lines` expects
+    fn refine(&self, corners: [(GraphCoord, f64); 4], depth: u32, lines: &mut Vec<Line>) {
+        let (min, max) = (corners[0].0, corners[2].0);
+        let centre = self.at((min.x + max.x) / 2.0, (min.y + max.y) / 2.0);
+
+        // the centre is checked as well so the curve can be found even if
+        // it leaves the cell through the same edge it came in
+        let crosses = |values: &[(GraphCoord, f64)]| {
+            values.iter().any(|&(_, z)| z >= 0.0) && values.iter().any(|&(_, z)| z < 0.0)
+        };
+        if !crosses(&[corners[0], corners[1], corners[2], corners[3], centre]) {
+            return;
+        }
+
+        if depth >= self.max_depth {
+            for line in marching_squares::cell_lines(&corners, 0.0) {
+                if !self.is_pole(&line, &corners) {
+                    lines.push(line);
+                }
+            }
+            return;
+        }
+
+        let mid = |a: (GraphCoord, f64), b: (GraphCoord, f64)| self.at((a.0.x + b.0.x) / 2.0, (a.0.y + b.0.y) / 2.0);
+        let bottom = mid(corners[0], corners[1]);
+        let right = mid(corners[1], corners[2]);
+        let top = mid(corners[2], corners[3]);
+        let left = mid(corners[3], corners[0]);
+
+        self.refine([corners[0], bottom, centre, left], depth + 1, lines);
+        self.refine([bottom, corners[1], right, centre], depth + 1, lines);
+        self.refine([centre, right, corners[2], top], depth + 1, lines);
+        self.refine([left, centre, top, corners[3]], depth + 1, lines);
+    }
+
+    // A function such as 1 / x changes sign without ever being zero, if the
+    // function is larger in the middle of the line than at any corner of
+    // the cell it's jumping across a pole rather than crossing zero
+    fn is_pole(&self, line: &Line, corners: &[(GraphCoord, f64); 4]) -> bool {
+        let Line(a, b) = *line;
+        let (_, z) = self.at((a.x + b.x) / 2.0, (a.y + b.y) / 2.0);
+        let largest = corners.iter().fold(0.0, |acc: f64, &(_, z)| acc.max(z.abs()));
+
+        !z.is_finite() || z.abs() > largest
+    }
+}
+
+impl <'a, F> Plottable for ImplicitCurve<'a, F> where F: Fn(f64, f64) -> f64 {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        canvas.set_color(self.opts.color);
+
+        for line in self.segments(bounds) {
+            line.plot(bounds, canvas)?;
+        }
+
+        Ok(())
+    }
+
+    fn data_extent(&self) -> Option<(GraphCoord, GraphCoord)> {
+        self.region
+    }
+
+    fn legend_entries(&self) -> Vec<LegendEntry<'_>> {
+        vec!(LegendEntry::new(self.opts.name, self.opts.color))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::mock_canvas::MockCanvas;
+    use graph_dimensions::GraphDimensions;
+    use options::DataSetOptions;
+    use pixel::GraphCoord;
+    use plottable::Plottable;
+
+    fn bounds(min: (f64, f64), max: (f64, f64)) -> GraphDimensions {
+        let mut bounds = GraphDimensions::new(600.0, 600.0);
+        bounds.min = GraphCoord::new(min.0, min.1);
+        bounds.max = GraphCoord::new(max.0, max.1);
+        bounds
+    }
+
+    #[test]
+    fn test_circle() {
+        let opts = DataSetOptions::default();
+        let circle = ImplicitCurve::new(&opts, |x: f64, y: f64| x * x + y * y - 1.0);
+
+        let segments = circle.segments(&bounds((-2.0, -2.0), (2.0, 2.0)));
+        assert!(segments.len() > 100);
+
+        // every end of every segment should be on the circle
+        let on_circle = |p: GraphCoord| ((p.x * p.x + p.y * p.y).sqrt() - 1.0).abs() < 1e-3;
+        assert!(segments.iter().all(|&Line(a, b)| on_circle(a) && on_circle(b)));
+
+        // zooming in should only find the part of the circle in view
+        let zoomed = circle.segments(&bounds((0.5, 0.5), (1.0, 1.0)));
+        assert!(!zoomed.is_empty());
+        assert!(zoomed.iter().all(|&Line(a, _)| a.x >= 0.5 && a.y >= 0.5));
+    }
+
+    #[test]
+    fn test_poles() {
+        let opts = DataSetOptions::default();
+
+        // 1 / x changes sign at x = 0 but is never zero
+        let curve = ImplicitCurve::new(&opts, |x: f64, _| 1.0 / x);
+        assert_eq!(curve.segments(&bounds((-1.0, -1.0), (1.0, 1.0))), vec!());
+    }
+
+    #[test]
+    fn test_plot() {
+        let mut fake_canvas = MockCanvas::new();
+        let opts = DataSetOptions::default().name("lemniscate");
+
+        let region = (GraphCoord::new(-1.5, -1.0), GraphCoord::new(1.5, 1.0));
+        let lemniscate = ImplicitCurve::new(&opts, |x: f64, y: f64| (x * x + y * y).powi(2) - 2.0 * (x * x - y * y))
+            .region(region.0, region.1);
+
+        assert_eq!(lemniscate.data_extent(), Some(region));
+        let dims = bounds((-1.5, -1.0), (1.5, 1.0));
+        assert_eq!(lemniscate.plot(&dims, &mut fake_canvas), Ok(()));
+    }
+}
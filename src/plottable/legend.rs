@@ -3,15 +3,51 @@ use data_set::DataSet;
 use graph_dimensions::GraphDimensions;
 use pixel::{Pixel, Color};
 use canvas::Canvas;
+use labeller::Labeller;
+use plottable::primitives::Circle;
+use plottable::graphs::BubbleSeries;
+
+/// A single row in a `Legend` for anything that isn't a `DataSet`
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct LegendEntry<'a> {
+    pub name: &'a str,
+    pub color: Color,
+    /// The colour of the second half of the swatch, for anything drawn in two colours
+    pub split: Option<Color>,
+}
+
+impl <'a> LegendEntry<'a> {
+    pub fn new(name: &'a str, color: Color) -> LegendEntry<'a> {
+        LegendEntry { name: name, color: color, split: None }
+    }
 
-pub struct Legend<'a>(pub &'a [&'a DataSet<'a>]);
+    /// Draws the right half of the swatch in `color`
+    pub fn split(mut self, color: Color) -> Self {
+        self.split = Some(color);
+        self
+    }
+}
+
+/// `Legend` lists every named `DataSet` followed by any extra entries
+/// nothing is drawn if there is nothing with a name
+pub struct Legend<'a>(pub &'a [&'a DataSet<'a>], pub &'a [LegendEntry<'a>]);
 
 impl<'a> Plottable for Legend<'a> {
     fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let mut rows = self.0.iter()
+            .map(|&ds| LegendEntry::new(ds.options.name, ds.choose_color()))
+            .collect::<Vec<_>>();
+        rows.extend(self.1.iter().cloned());
+        rows.retain(|row| !row.name.is_empty());
+
+        if rows.is_empty() {
+            return Ok(());
+        }
+
         let Pixel { x: top_x, y: top_y } = 
             bounds.convert_to_pixel((bounds.max.x, bounds.max.y)).expect("bounds.max should be on grid");
 
-        let count = self.0.iter().filter(|&ds| ds.options.name != "").collect::<Vec<_>>().len();
+        let count = rows.len();
 
         let row_height = bounds.height * 0.025;
         let title_height = bounds.height * 0.0375;
@@ -35,14 +71,21 @@ impl<'a> Plottable for Legend<'a> {
         canvas.write_text("Legend", (x, y + (height - title_height)))?;
 
         let mut y = y + 3.0;
-        for &ds in self.0.iter() {
+        for row in rows.iter() {
             let half_y = y + (row_height / 2.0);
             
-            canvas.set_color(ds.choose_color());
-            canvas.draw_line((x, half_y), (x + 20.0, half_y))?;
+            canvas.set_color(row.color);
+            match row.split {
+                Some(split) => {
+                    canvas.draw_line((x, half_y), (x + 10.0, half_y))?;
+                    canvas.set_color(split);
+                    canvas.draw_line((x + 10.0, half_y), (x + 20.0, half_y))?;
+                },
+                None => canvas.draw_line((x, half_y), (x + 20.0, half_y))?,
+            }
             
             canvas.set_color(Color(0, 0, 0));
-            canvas.write_text(ds.options.name, (x + 25.0, y))?;
+            canvas.write_text(row.name, (x + 25.0, y))?;
             y += row_height;
         }
 
@@ -50,6 +93,64 @@ impl<'a> Plottable for Legend<'a> {
     }
 }
 
+/// `BubbleLegend` shows a few bubbles with a reference size next to them
+/// in the bottom right of the graph so the size of a `BubbleSeries` can be read
+pub struct BubbleLegend<'a>(pub &'a DataSet<'a>);
+
+impl <'a> BubbleLegend<'a> {
+    /// Up to three nice looking sizes, chosen by the `Labeller`, that are
+    /// no bigger than the largest size in the `DataSet`
+    pub fn reference_sizes(&self) -> Vec<f64> {
+        let max_size = self.0.get_max_size();
+        if max_size <= 0.0 {
+            return vec!();
+        }
+
+        let label = Labeller::in_base10().search(0.0, max_size, 3);
+        let mut sizes = Vec::new();
+        let mut size = label.step;
+        while label.step > 0.0 && size <= max_size {
+            sizes.push(size);
+            size += label.step;
+        }
+
+        let skip = sizes.len().saturating_sub(3);
+        sizes.into_iter().skip(skip).collect()
+    }
+}
+
+impl <'a> Plottable for BubbleLegend<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let ds = self.0;
+        let (bottom_left, top_right) = bounds.plot_area();
+        let max_size = ds.get_max_size();
+        let gap = 5.0;
+
+        let sizes = self.reference_sizes();
+        let widest = sizes.iter()
+            .map(|&size| BubbleSeries::radius_for(ds.options, size, max_size))
+            .fold(0.0, |acc: f64, r| acc.max(r));
+        let x = top_right.x - widest - (bounds.width * 0.05);
+
+        canvas.set_color(Color(0, 0, 0));
+        let mut y = bottom_left.y + gap;
+        for &size in sizes.iter() {
+            let radius = BubbleSeries::radius_for(ds.options, size, max_size);
+            let centre = Pixel::new(x, y + radius);
+
+            let outline = Circle::outline(centre, radius);
+            for n in 0..outline.len() {
+                canvas.draw_line(outline[n], outline[(n + 1) % outline.len()])?;
+            }
+            canvas.write_num_centred(size, (x - widest - (bounds.width * 0.03), centre.y))?;
+
+            y += 2.0 * radius + gap;
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,8 +177,8 @@ mod tests {
         dims.max = GraphCoord::new(1.0, 2.0);
         dims.min = GraphCoord::new(1.0, 2.0);
 
-        let legend_fail = Legend(data_set_fail);
-        let legend_pass = Legend(data_set_pass);
+        let legend_fail = Legend(data_set_fail, &[]);
+        let legend_pass = Legend(data_set_pass, &[]);
 
         assert_eq!(legend_pass.plot(&dims, &mut fake_canvas), Ok(()));
         assert_eq!(legend_fail.plot(&dims, &mut fake_canvas), Err(MockError("write_text failed".to_string())));
@@ -88,4 +189,38 @@ mod tests {
         assert_eq!(fake_canvas.shown, 0);
         assert_eq!(fake_canvas.cleared, 0)
     }
+
+    #[test]
+    fn test_bubble_legend() {
+        let opts = DataSetOptions::default();
+        let ds = DataSet::from_vecs(vec!(1.0, 2.0), vec!(1.0, 2.0), &opts).unwrap()
+            .sizes(vec!(10.0, 95.0)).unwrap();
+
+        let sizes = BubbleLegend(&ds).reference_sizes();
+        assert!(!sizes.is_empty() && sizes.len() <= 3);
+        assert!(sizes.iter().all(|&size| size > 0.0 && size <= 95.0));
+
+        // without any sizes there is nothing to show
+        let ds = DataSet::from_vecs(vec!(1.0), vec!(1.0), &opts).unwrap();
+        assert_eq!(BubbleLegend(&ds).reference_sizes(), vec!());
+    }
+
+    #[test]
+    fn test_legend_entries() {
+        let mut fake_canvas = MockCanvas::new();
+        let mut dims = GraphDimensions::new(600.0, 600.0);
+        dims.max = GraphCoord::new(1.0, 2.0);
+        dims.min = GraphCoord::new(0.0, 0.0);
+
+        // extra entries should be written the same as data sets
+        let entries = &[LegendEntry::new("fail", Color(255, 0, 0))];
+        assert_eq!(Legend(&[], entries).plot(&dims, &mut fake_canvas),
+                   Err(MockError("write_text failed".to_string())));
+
+        // with nothing named nothing should be drawn
+        let unnamed = &[LegendEntry::new("", Color(255, 0, 0))];
+        let mut fake_canvas = MockCanvas::new();
+        assert_eq!(Legend(&[], unnamed).plot(&dims, &mut fake_canvas), Ok(()));
+        assert_eq!(fake_canvas.color, Color(1, 1, 1));
+    }
 }
\ No newline at end of file
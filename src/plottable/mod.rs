@@ -2,16 +2,72 @@ pub mod axis;
 pub mod primitives;
 pub mod graphs;
 pub mod legend;
+pub mod contour;
+pub mod fill_between;
+pub mod bar_chart;
+pub mod financial;
+pub mod radar;
+pub mod quiver;
+pub mod density;
+pub mod function;
+pub mod implicit;
+pub mod waterfall;
+pub mod timeline;
+pub mod distribution;
+pub mod treemap;
+pub mod graphs_3d;
+pub mod parallel;
+pub mod sankey;
+pub mod network;
 
-pub use self::axis::Axis;
-pub use self::legend::Legend;
+pub use self::axis::{Axis, Category};
+pub use self::legend::{Legend, LegendEntry, BubbleLegend};
+pub use self::contour::ContourSeries;
+pub use self::fill_between::FillBetween;
+pub use self::bar_chart::BarChart;
+pub use self::financial::{Ohlc, CandlestickSeries, OhlcSeries};
+pub use self::radar::{RadarChart, RadarRecord};
+pub use self::quiver::QuiverSeries;
+pub use self::density::{Hist2dSeries, HexbinSeries};
+pub use self::function::FunctionSeries;
+pub use self::implicit::ImplicitCurve;
+pub use self::waterfall::{WaterfallSeries, WaterfallStep};
+pub use self::timeline::{TimelineSeries, Task, Milestone};
+pub use self::distribution::{EcdfSeries, QqSeries, Distribution};
+pub use self::treemap::{Treemap, TreeNode, Tile};
+pub use self::graphs_3d::{Plottable3D, Shape3D, SurfaceSeries, WireframeSeries, Line3DSeries, Scatter3DSeries};
+pub use self::parallel::{ParallelCoordinates, ParallelRecord, ParallelColor};
+pub use self::sankey::{Sankey, SankeyNode, SankeyLink, NodeBox, Band};
+pub use self::network::{NetworkGraph, NetworkNode, NetworkEdge, NetworkLayout};
 
 use canvas::Canvas;
 use graph_dimensions::GraphDimensions;
 use data_set::DataSet;
+use pixel::GraphCoord;
 
 pub trait Plottable {
     fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err>;
+
+    /// The minimum and maximum `GraphCoord` that this will draw, this is used
+    /// by `Graph2D` to fit the view around anything that isn't a `DataSet`
+    fn data_extent(&self) -> Option<(GraphCoord, GraphCoord)> {
+        None
+    }
+
+    /// Any rows that should be added to the `Legend` of a `Graph2D` for this
+    fn legend_entries(&self) -> Vec<LegendEntry<'_>> {
+        vec!()
+    }
+
+    /// Labels written along the x axis of a `Graph2D` in place of its numbers
+    fn x_categories(&self) -> Vec<(f64, Category<'_>)> {
+        vec!()
+    }
+
+    /// Labels written along the y axis of a `Graph2D` in place of its numbers
+    fn y_categories(&self) -> Vec<(f64, Category<'_>)> {
+        vec!()
+    }
 }
 
 pub trait HasDataSet {
@@ -0,0 +1,456 @@
+use plottable::Plottable;
+use plottable::primitives::Circle;
+use colormap::PALETTE;
+use graph_dimensions::GraphDimensions;
+use canvas::Canvas;
+use pixel::{Color, GraphCoord, Pixel};
+use utils;
+use std::cmp::Ordering;
+use std::f64;
+use std::f64::consts::PI;
+
+// The amount of steps the force directed layout takes to settle
+const ITERATIONS: usize = 300;
+// The furthest a node can move in the first step, this shrinks to nothing by the last
+const START_TEMPERATURE: f64 = 0.1;
+// The length of each arrowhead in pixels, before it is grown for wider edges
+const ARROW_SIZE: f64 = 8.0;
+
+/// A node of a `NetworkGraph`
+#[derive(Clone, PartialEq, Debug)]
+pub struct NetworkNode<'a> {
+    pub name: &'a str,
+    pub color: Option<Color>,
+}
+
+impl <'a> NetworkNode<'a> {
+    pub fn new(name: &'a str) -> NetworkNode<'a> {
+        NetworkNode { name: name, color: None }
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+/// An edge between the nodes at index `source` and `target`, the edges
+/// with the largest weight are drawn the widest
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct NetworkEdge {
+    pub source: usize,
+    pub target: usize,
+    pub weight: f64,
+}
+
+impl NetworkEdge {
+    pub fn new(source: usize, target: usize) -> NetworkEdge {
+        NetworkEdge { source: source, target: target, weight: 1.0 }
+    }
+
+    pub fn weight(mut self, weight: f64) -> Self {
+        self.weight = weight;
+        self
+    }
+}
+
+/// How the nodes of a `NetworkGraph` are laid out
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum NetworkLayout {
+    /// The Fruchterman-Reingold layout, every node pushes every other node away
+    /// and each edge pulls its nodes together until they settle
+    ForceDirected,
+    /// Every node is evenly spaced around a circle in order
+    Circular,
+    /// Each node is put in a row below everything with an edge into it,
+    /// which suits a directed graph without any cycles
+    Hierarchical,
+}
+
+/// `NetworkGraph` draws a marker for each node, labelled with its name, and
+/// a line for each edge, the nodes are laid out between (0, 0) and (1, 1)
+/// using the given `NetworkLayout` and can be moved afterwards, such as by
+/// dragging them with the mouse
+pub struct NetworkGraph<'a> {
+    nodes: &'a [NetworkNode<'a>],
+    edges: &'a [NetworkEdge],
+    positions: Vec<GraphCoord>,
+    directed: bool,
+    node_size: f64,
+    max_edge_width: f64,
+    edge_color: Color,
+}
+
+impl <'a> NetworkGraph<'a> {
+    pub fn new(nodes: &'a [NetworkNode<'a>], edges: &'a [NetworkEdge], layout: NetworkLayout) -> Self {
+        NetworkGraph {
+            nodes: nodes,
+            edges: edges,
+            positions: Vec::new(),
+            directed: false,
+            node_size: 6.0,
+            max_edge_width: 4.0,
+            edge_color: Color(120, 120, 120),
+        }.layout(layout)
+    }
+
+    /// Lays the nodes out again, replacing anywhere they have been moved to
+    pub fn layout(mut self, layout: NetworkLayout) -> Self {
+        self.positions = match layout {
+            NetworkLayout::ForceDirected => self.force_directed(),
+            NetworkLayout::Circular => self.circular(),
+            NetworkLayout::Hierarchical => self.hierarchical(),
+        };
+        self
+    }
+
+    /// Draws an arrowhead at the target end of each edge
+    pub fn directed(mut self, directed: bool) -> Self {
+        self.directed = directed;
+        self
+    }
+
+    /// The radius of each node in pixels
+    pub fn node_size(mut self, node_size: f64) -> Self {
+        self.node_size = node_size.max(0.0);
+        self
+    }
+
+    /// The width in pixels of the edge with the largest weight,
+    /// every other edge is narrower in proportion to its weight
+    pub fn max_edge_width(mut self, max_edge_width: f64) -> Self {
+        self.max_edge_width = max_edge_width.max(1.0);
+        self
+    }
+
+    pub fn edge_color(mut self, color: Color) -> Self {
+        self.edge_color = color;
+        self
+    }
+
+    pub fn positions(&self) -> &[GraphCoord] {
+        &self.positions
+    }
+
+    /// Moves a node to `position`
+    pub fn move_node(&mut self, node: usize, position: GraphCoord) {
+        if let Some(p) = self.positions.get_mut(node) {
+            *p = position;
+        }
+    }
+
+    /// The node drawn under `pix`, if there is one, the nearest wins if they overlap
+    pub fn node_at(&self, bounds: &GraphDimensions, pix: Pixel) -> Option<usize> {
+        let reach = self.node_size + 3.0;
+
+        self.positions.iter().enumerate()
+            .filter_map(|(n, &p)| bounds.convert_to_pixel(p).map(|node| (n, (node.x - pix.x).hypot(node.y - pix.y))))
+            .filter(|&(_, distance)| distance <= reach)
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+            .map(|(n, _)| n)
+    }
+
+    /// The dimensions of the network on a canvas of `width` by `height` pixels
+    /// with enough room around the edge for the markers and labels
+    pub fn dimensions(&self, width: f64, height: f64) -> GraphDimensions {
+        let mut dims = GraphDimensions::new(width, height);
+        if let Some((min, max)) = self.data_extent() {
+            dims.adjust_for_extent(min, max);
+        }
+
+        // a single node, or nodes in a line, still need some room to be drawn in
+        if dims.max.x <= dims.min.x {
+            dims.min.x -= 0.5;
+            dims.max.x += 0.5;
+        }
+        if dims.max.y <= dims.min.y {
+            dims.min.y -= 0.5;
+            dims.max.y += 0.5;
+        }
+
+        let border = self.node_size + 30.0;
+        dims.set_border_pixels(border, border);
+        dims
+    }
+
+    /// Clears the whole canvas and draws the network on it
+    pub fn show<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        canvas.set_color(Color(255, 255, 255));
+        canvas.clear();
+
+        self.plot(bounds, canvas)?;
+        canvas.show();
+        Ok(())
+    }
+
+    // Edges that join two different nodes that exist
+    fn valid_edges<'e>(&'e self) -> Box<dyn Iterator<Item=NetworkEdge> + 'e> {
+        let n = self.nodes.len();
+        Box::new(self.edges.iter().cloned().filter(move |e| e.source < n && e.target < n && e.source != e.target))
+    }
+
+    fn circular(&self) -> Vec<GraphCoord> {
+        let n = self.nodes.len() as f64;
+
+        // start at the top and go clockwise
+        (0..self.nodes.len()).map(|i| {
+            let theta = PI / 2.0 - 2.0 * PI * (i as f64) / n;
+            GraphCoord::new(0.5 + 0.5 * theta.cos(), 0.5 + 0.5 * theta.sin())
+        }).collect()
+    }
+
+    fn force_directed(&self) -> Vec<GraphCoord> {
+        let n = self.nodes.len();
+        if n == 0 {
+            return Vec::new();
+        }
+
+        // the nodes start in a sunflower spiral, which doesn't depend on any
+        // randomness and has no symmetry for the forces to get stuck in
+        let golden_angle = PI * (3.0 - 5f64.sqrt());
+        let mut positions = (0..n).map(|i| {
+            let r = 0.5 * (((i as f64) + 0.5) / (n as f64)).sqrt();
+            let theta = (i as f64) * golden_angle;
+            GraphCoord::new(0.5 + r * theta.cos(), 0.5 + r * theta.sin())
+        }).collect::<Vec<_>>();
+
+        let edges = self.valid_edges().collect::<Vec<_>>();
+        let k = (1.0 / (n as f64)).sqrt();
+
+        for iteration in 0..ITERATIONS {
+            let mut moves = vec!((0.0, 0.0); n);
+
+            for i in 0..n {
+                for j in (i + 1)..n {
+                    let (dx, dy) = (positions[i].x - positions[j].x, positions[i].y - positions[j].y);
+                    let distance = dx.hypot(dy).max(1e-6);
+                    let force = k * k / distance;
+
+                    moves[i].0 += dx / distance * force;
+                    moves[i].1 += dy / distance * force;
+                    moves[j].0 -= dx / distance * force;
+                    moves[j].1 -= dy / distance * force;
+                }
+            }
+
+            for edge in edges.iter() {
+                let (s, t) = (edge.source, edge.target);
+                let (dx, dy) = (positions[s].x - positions[t].x, positions[s].y - positions[t].y);
+                let distance = dx.hypot(dy).max(1e-6);
+                let force = distance * distance / k;
+
+                moves[s].0 -= dx / distance * force;
+                moves[s].1 -= dy / distance * force;
+                moves[t].0 += dx / distance * force;
+                moves[t].1 += dy / distance * force;
+            }
+
+            // the nodes can move less each step so the layout settles down
+            let temperature = START_TEMPERATURE * (1.0 - (iteration as f64) / (ITERATIONS as f64));
+            for (p, &(dx, dy)) in positions.iter_mut().zip(moves.iter()) {
+                let length = dx.hypot(dy);
+                if length > 0.0 {
+                    let step = length.min(temperature);
+                    p.x = (p.x + dx / length * step).clamp(0.0, 1.0);
+                    p.y = (p.y + dy / length * step).clamp(0.0, 1.0);
+                }
+            }
+        }
+
+        fit_to_unit_square(&positions)
+    }
+
+    fn hierarchical(&self) -> Vec<GraphCoord> {
+        let n = self.nodes.len();
+        let edges = self.valid_edges().collect::<Vec<_>>();
+
+        // each node goes one row below the furthest node with an edge into it
+        let pairs = edges.iter().map(|e| (e.source, e.target)).collect::<Vec<_>>();
+        let rows = utils::longest_path_layers(n, &pairs);
+
+        let row_count = rows.iter().cloned().max().map_or(0, |r| r + 1);
+        let mut positions = vec!(GraphCoord::new(0.5, 0.5); n);
+
+        // each row is ordered by the average position of the nodes above that
+        // point into it, which keeps the amount of crossing edges down
+        for row in 0..row_count {
+            let mut nodes = (0..n).filter(|&i| rows[i] == row).map(|i| {
+                let parents = edges.iter().filter(|e| e.target == i && rows[e.source] < row).collect::<Vec<_>>();
+                let order = if parents.is_empty() {
+                    f64::MAX
+                } else {
+                    parents.iter().map(|e| positions[e.source].x).sum::<f64>() / (parents.len() as f64)
+                };
+                (i, order)
+            }).collect::<Vec<_>>();
+
+            nodes.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal));
+
+            let y = if row_count > 1 { 1.0 - (row as f64) / ((row_count - 1) as f64) } else { 0.5 };
+            for (place, &(i, _)) in nodes.iter().enumerate() {
+                let x = ((place as f64) + 1.0) / ((nodes.len() as f64) + 1.0);
+                positions[i] = GraphCoord::new(x, y);
+            }
+        }
+
+        positions
+    }
+
+    fn edge_width(&self, weight: f64, max_weight: f64) -> f64 {
+        if max_weight > 0.0 && weight > 0.0 {
+            1.0 + (self.max_edge_width - 1.0) * (weight / max_weight).min(1.0)
+        } else {
+            1.0
+        }
+    }
+
+    fn draw_edge<C: Canvas>(&self, start: Pixel, end: Pixel, width: f64, canvas: &mut C) -> Result<(), C::Err> {
+        let (dx, dy) = (end.x - start.x, end.y - start.y);
+        let length = dx.hypot(dy);
+        if length <= 2.0 * self.node_size {
+            return Ok(());
+        }
+
+        // edges run between the outsides of the markers, not their centres
+        let (ux, uy) = (dx / length, dy / length);
+        let start = Pixel::new(start.x + ux * self.node_size, start.y + uy * self.node_size);
+        let mut end = Pixel::new(end.x - ux * self.node_size, end.y - uy * self.node_size);
+
+        if self.directed {
+            let head = (ARROW_SIZE + 2.0 * width).min((length - 2.0 * self.node_size) * 0.5);
+            let base = Pixel::new(end.x - ux * head, end.y - uy * head);
+            let half = head * 0.5;
+            canvas.fill_polygon(&[end, Pixel::new(base.x - uy * half, base.y + ux * half),
+                                  Pixel::new(base.x + uy * half, base.y - ux * half)])?;
+            end = base;
+        }
+
+        if width <= 1.0 {
+            return canvas.draw_line(start, end);
+        }
+
+        let (nx, ny) = (-uy * width / 2.0, ux * width / 2.0);
+        canvas.fill_polygon(&[Pixel::new(start.x + nx, start.y + ny), Pixel::new(end.x + nx, end.y + ny),
+                              Pixel::new(end.x - nx, end.y - ny), Pixel::new(start.x - nx, start.y - ny)])
+    }
+}
+
+// Scales the points, keeping their shape, so they just fit between (0, 0) and (1, 1)
+fn fit_to_unit_square(points: &[GraphCoord]) -> Vec<GraphCoord> {
+    let (min_x, max_x) = points.iter().fold((f64::MAX, f64::MIN), |(min, max), p| (min.min(p.x), max.max(p.x)));
+    let (min_y, max_y) = points.iter().fold((f64::MAX, f64::MIN), |(min, max), p| (min.min(p.y), max.max(p.y)));
+    let range = (max_x - min_x).max(max_y - min_y);
+    if range <= 0.0 || range.is_nan() {
+        return points.iter().map(|_| GraphCoord::new(0.5, 0.5)).collect();
+    }
+
+    let (offset_x, offset_y) = ((range - (max_x - min_x)) / 2.0, (range - (max_y - min_y)) / 2.0);
+    points.iter()
+        .map(|p| GraphCoord::new((p.x - min_x + offset_x) / range, (p.y - min_y + offset_y) / range))
+        .collect()
+}
+
+impl <'a> Plottable for NetworkGraph<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let max_weight = self.valid_edges().fold(0.0, |acc: f64, e| acc.max(e.weight));
+
+        canvas.set_color(self.edge_color);
+        for edge in self.valid_edges() {
+            let start = bounds.convert_to_pixel(self.positions[edge.source]);
+            let end = bounds.convert_to_pixel(self.positions[edge.target]);
+
+            if let (Some(start), Some(end)) = (start, end) {
+                self.draw_edge(start, end, self.edge_width(edge.weight, max_weight), canvas)?;
+            }
+        }
+
+        for (node, &position) in self.nodes.iter().zip(self.positions.iter()) {
+            if let Some(pix) = bounds.convert_to_pixel(position) {
+                canvas.set_color(node.color.unwrap_or(PALETTE[0]));
+                Circle(position, self.node_size).plot(bounds, canvas)?;
+
+                canvas.set_color(Color(0, 0, 0));
+                canvas.write_text_centred(node.name, Pixel::new(pix.x, pix.y + self.node_size + 10.0))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn data_extent(&self) -> Option<(GraphCoord, GraphCoord)> {
+        if self.positions.is_empty() {
+            return None;
+        }
+
+        let (min_x, max_x) = self.positions.iter().fold((f64::MAX, f64::MIN), |(min, max), p| (min.min(p.x), max.max(p.x)));
+        let (min_y, max_y) = self.positions.iter().fold((f64::MAX, f64::MIN), |(min, max), p| (min.min(p.y), max.max(p.y)));
+        Some((GraphCoord::new(min_x, min_y), GraphCoord::new(max_x, max_y)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::mock_canvas::MockCanvas;
+    use pixel::{Color, GraphCoord};
+    use plottable::Plottable;
+
+    #[test]
+    fn test_layouts() {
+        let nodes = [NetworkNode::new("core"), NetworkNode::new("io"), NetworkNode::new("net"), NetworkNode::new("app")];
+        let edges = [NetworkEdge::new(3, 1), NetworkEdge::new(3, 2), NetworkEdge::new(1, 0), NetworkEdge::new(2, 0)];
+
+        let circular = NetworkGraph::new(&nodes, &edges, NetworkLayout::Circular);
+        assert_eq!(circular.positions()[0], GraphCoord::new(0.5, 1.0));
+
+        // app depends on everything so it is on the top row and core on the bottom
+        let hierarchical = NetworkGraph::new(&nodes, &edges, NetworkLayout::Hierarchical);
+        let positions = hierarchical.positions();
+        assert_eq!(positions[3], GraphCoord::new(0.5, 1.0));
+        assert_eq!(positions[0], GraphCoord::new(0.5, 0.0));
+        assert_eq!((positions[1].y, positions[2].y), (0.5, 0.5));
+
+        // the force directed layout should fill the square and keep nodes apart
+        let force = NetworkGraph::new(&nodes, &edges, NetworkLayout::ForceDirected);
+        let positions = force.positions();
+        let (min, max) = force.data_extent().unwrap();
+        assert!(min.x >= 0.0 && min.y >= 0.0 && max.x <= 1.0 && max.y <= 1.0);
+        assert!((max.x - min.x - 1.0).abs() < 1e-9 || (max.y - min.y - 1.0).abs() < 1e-9);
+        for i in 0..positions.len() {
+            for j in (i + 1)..positions.len() {
+                assert!((positions[i].x - positions[j].x).hypot(positions[i].y - positions[j].y) > 0.1);
+            }
+        }
+    }
+
+    #[test]
+    fn test_move_node() {
+        let nodes = [NetworkNode::new("a"), NetworkNode::new("b")];
+        let edges = [NetworkEdge::new(0, 1).weight(2.0)];
+        let mut network = NetworkGraph::new(&nodes, &edges, NetworkLayout::Circular);
+        let dims = network.dimensions(400.0, 400.0);
+
+        let pix = dims.convert_to_pixel(network.positions()[1]).unwrap();
+        assert_eq!(network.node_at(&dims, Pixel::new(pix.x + 2.0, pix.y - 2.0)), Some(1));
+        assert_eq!(network.node_at(&dims, Pixel::new(200.0, 200.0)), None);
+
+        network.move_node(1, GraphCoord::new(0.25, 0.5));
+        assert_eq!(network.positions()[1], GraphCoord::new(0.25, 0.5));
+    }
+
+    #[test]
+    fn test_plot() {
+        let mut fake_canvas = MockCanvas::new();
+        let nodes = [NetworkNode::new("parser").color(Color(255, 0, 0)), NetworkNode::new("lexer"), NetworkNode::new("ast")];
+        let edges = [NetworkEdge::new(0, 1).weight(3.0), NetworkEdge::new(0, 2), NetworkEdge::new(1, 1), NetworkEdge::new(0, 7)];
+        let network = NetworkGraph::new(&nodes, &edges, NetworkLayout::ForceDirected).directed(true);
+
+        assert_eq!(network.edge_width(3.0, 3.0), 4.0);
+        assert_eq!(network.edge_width(0.0, 3.0), 1.0);
+
+        let dims = network.dimensions(600.0, 400.0);
+        assert_eq!(network.show(&dims, &mut fake_canvas), Ok(()));
+        assert_eq!(fake_canvas.color, Color(0, 0, 0));
+        assert_eq!((fake_canvas.shown, fake_canvas.cleared), (1, 1));
+    }
+}
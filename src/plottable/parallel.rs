@@ -0,0 +1,292 @@
+use plottable::{Plottable, LegendEntry};
+use colormap::{Colormap, PALETTE};
+use labeller::{Labeller, Label};
+use graph_dimensions::GraphDimensions;
+use canvas::Canvas;
+use pixel::{Color, GraphCoord, Pixel};
+use std::f64;
+
+/// `ParallelRecord` is a single line on a `ParallelCoordinates` plot
+/// with one value for each dimension
+#[derive(Clone, PartialEq, Debug)]
+pub struct ParallelRecord<'a> {
+    pub values: Vec<f64>,
+    pub category: &'a str,
+}
+
+impl <'a> ParallelRecord<'a> {
+    pub fn new(values: Vec<f64>) -> ParallelRecord<'a> {
+        ParallelRecord { values: values, category: "" }
+    }
+
+    /// The category of the record, used when lines are coloured by category
+    pub fn category(mut self, category: &'a str) -> Self {
+        self.category = category;
+        self
+    }
+}
+
+/// How the line for each record of a `ParallelCoordinates` plot is coloured
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ParallelColor {
+    /// Every line is the same colour
+    Fixed(Color),
+    /// Each line is coloured by its value of a dimension using the colormap
+    Dimension(usize),
+    /// Each category is given its own colour and a row in the legend
+    Category,
+}
+
+/// `ParallelCoordinates` draws a vertical axis for each dimension, side by side,
+/// each with its own scale, and each record as a line joining its value on every axis
+/// It lays itself out across the whole graph so it should be added to a
+/// `Graph2D` without any `AxisOptions`
+pub struct ParallelCoordinates<'a> {
+    dimensions: &'a [&'a str],
+    records: &'a [ParallelRecord<'a>],
+    ranges: Vec<Option<(f64, f64)>>,
+    inverted: Vec<bool>,
+    tick_count: i32,
+    color: ParallelColor,
+    colormap: Colormap,
+}
+
+impl <'a> ParallelCoordinates<'a> {
+    pub fn new(dimensions: &'a [&'a str], records: &'a [ParallelRecord<'a>]) -> Self {
+        ParallelCoordinates {
+            dimensions: dimensions,
+            records: records,
+            ranges: vec!(None; dimensions.len()),
+            inverted: vec!(false; dimensions.len()),
+            tick_count: 5,
+            color: ParallelColor::Fixed(Color(0, 0, 255)),
+            colormap: Colormap::default(),
+        }
+    }
+
+    /// Sets the scale of a single axis instead of choosing it from the records
+    pub fn axis_range(mut self, axis: usize, min: f64, max: f64) -> Self {
+        if axis < self.ranges.len() {
+            self.ranges[axis] = Some((min, max));
+        }
+        self
+    }
+
+    /// Puts the largest value of an axis at the bottom
+    pub fn invert(mut self, axis: usize, inverted: bool) -> Self {
+        if axis < self.inverted.len() {
+            self.inverted[axis] = inverted;
+        }
+        self
+    }
+
+    /// The amount of ticks the `Labeller` should aim for on each axis
+    pub fn tick_count(mut self, tick_count: i32) -> Self {
+        self.tick_count = tick_count;
+        self
+    }
+
+    pub fn color(mut self, color: ParallelColor) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// The colormap used when the lines are coloured by a dimension
+    pub fn colormap(mut self, colormap: Colormap) -> Self {
+        self.colormap = colormap;
+        self
+    }
+
+    /// The ticks on each axis, unless a range has been given this
+    /// is chosen by the `Labeller` to cover every record
+    pub fn labels(&self) -> Vec<Label> {
+        let mut labeller = Labeller::in_base10();
+        labeller.loose = true;
+
+        self.ranges.iter().enumerate().map(|(n, range)| {
+            // an empty range is widened the same as when every record has the same value
+            if let Some((min, max)) = *range {
+                if max <= min {
+                    return labeller.search(min - 1.0, min + 1.0, self.tick_count);
+                }
+
+                let mut label = labeller.search(min, max, self.tick_count);
+                label.min = min;
+                label.max = max;
+                return label;
+            }
+
+            let values = self.records.iter().filter_map(|r| r.values.get(n)).filter(|v| v.is_finite());
+            let (min, max) = values.fold((f64::MAX, f64::MIN), |(min, max): (f64, f64), &v| (min.min(v), max.max(v)));
+            if max < min {
+                return labeller.search(0.0, 1.0, self.tick_count);
+            }
+            if max == min {
+                return labeller.search(min - 1.0, max + 1.0, self.tick_count);
+            }
+
+            labeller.search(min, max, self.tick_count)
+        }).collect()
+    }
+
+    /// How far up axis `n` a value is, from 0 at the bottom to 1 at the top
+    pub fn fraction(&self, n: usize, value: f64, label: &Label) -> f64 {
+        let fraction = (value - label.min) / (label.max - label.min);
+        if self.inverted[n] { 1.0 - fraction } else { fraction }
+    }
+
+    /// The categories of the records in the order they first appear
+    pub fn categories(&self) -> Vec<&'a str> {
+        let mut categories = Vec::new();
+        for record in self.records.iter() {
+            if !categories.contains(&record.category) {
+                categories.push(record.category);
+            }
+        }
+        categories
+    }
+
+    fn record_color(&self, record: &ParallelRecord, labels: &[Label], categories: &[&str]) -> Color {
+        match self.color {
+            ParallelColor::Fixed(color) => color,
+            ParallelColor::Dimension(n) => {
+                let value = record.values.get(n).cloned().unwrap_or(f64::NAN);
+                labels.get(n).map_or(self.colormap.color_at(0.5), |l| self.colormap.color_for(value, l.min, l.max))
+            },
+            ParallelColor::Category => {
+                let n = categories.iter().position(|&c| c == record.category).unwrap_or(0);
+                PALETTE[n % PALETTE.len()]
+            },
+        }
+    }
+}
+
+impl <'a> Plottable for ParallelCoordinates<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        if self.dimensions.len() < 2 {
+            return Ok(());
+        }
+
+        let (bottom_left, top_right) = bounds.plot_area();
+        let gap = (top_right.x - bottom_left.x) / ((self.dimensions.len() - 1) as f64);
+        let height = top_right.y - bottom_left.y;
+        let at = |n: usize, fraction: f64| Pixel::new(bottom_left.x + gap * (n as f64), bottom_left.y + height * fraction);
+
+        let labels = self.labels();
+        let categories = self.categories();
+
+        // the lines go under the axes so the ticks can still be read
+        for record in self.records.iter() {
+            canvas.set_color(self.record_color(record, &labels, &categories));
+
+            let points = labels.iter().enumerate()
+                .map(|(n, label)| {
+                    let value = record.values.get(n).cloned().unwrap_or(f64::NAN);
+                    let fraction = self.fraction(n, value, label);
+                    if fraction.is_finite() { Some(at(n, fraction)) } else { None }
+                })
+                .collect::<Vec<_>>();
+
+            // a missing value leaves a gap either side of its axis
+            for pair in points.windows(2) {
+                if let (Some(a), Some(b)) = (pair[0], pair[1]) {
+                    canvas.draw_line(a, b)?;
+                }
+            }
+        }
+
+        canvas.set_color(Color(0, 0, 0));
+        for (n, (name, label)) in self.dimensions.iter().zip(labels.iter()).enumerate() {
+            canvas.draw_line(at(n, 0.0), at(n, 1.0))?;
+            canvas.write_text_centred(name, Pixel::new(at(n, 1.0).x, top_right.y + 15.0))?;
+
+            // a given range might not start on a step, the ticks still go on round numbers
+            let mut tick = (label.min / label.step).ceil() * label.step;
+            while label.step > 0.0 && tick <= label.max + label.step * 1e-9 {
+                let pix = at(n, self.fraction(n, tick, label));
+                canvas.draw_line(pix, Pixel::new(pix.x - 5.0, pix.y))?;
+                canvas.write_num_centred(tick, Pixel::new(pix.x - 25.0, pix.y))?;
+                tick += label.step;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn data_extent(&self) -> Option<(GraphCoord, GraphCoord)> {
+        Some((GraphCoord::new(0.0, 0.0), GraphCoord::new(1.0, 1.0)))
+    }
+
+    fn legend_entries(&self) -> Vec<LegendEntry<'_>> {
+        match self.color {
+            ParallelColor::Category => {
+                self.categories().into_iter().enumerate()
+                    .map(|(n, c)| LegendEntry::new(c, PALETTE[n % PALETTE.len()]))
+                    .collect()
+            },
+            _ => vec!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::mock_canvas::MockCanvas;
+    use graph_dimensions::GraphDimensions;
+    use pixel::{Color, GraphCoord};
+    use plottable::Plottable;
+
+    #[test]
+    fn test_labels() {
+        let dimensions = ["learning rate", "batch size", "loss"];
+        let records = [
+            ParallelRecord::new(vec!(0.01, 32.0, 0.8)),
+            ParallelRecord::new(vec!(0.1, 128.0, 0.3)),
+        ];
+
+        let plot = ParallelCoordinates::new(&dimensions, &records)
+            .axis_range(1, 0.0, 256.0)
+            .invert(2, true);
+        let labels = plot.labels();
+
+        // every automatic scale should cover every record
+        assert!(labels[0].min <= 0.01 && labels[0].max >= 0.1);
+        assert_eq!((labels[1].min, labels[1].max), (0.0, 256.0));
+        assert!(labels[2].min <= 0.3 && labels[2].max >= 0.8);
+
+        assert_eq!(plot.fraction(1, 64.0, &labels[1]), 0.25);
+
+        // an empty range is widened so there is still a scale
+        let plot = plot.axis_range(0, 3.0, 3.0);
+        let label = &plot.labels()[0];
+        assert!(label.min < 3.0 && label.max > 3.0);
+        assert!(plot.fraction(0, 3.0, label).is_finite());
+        // a smaller loss is higher up an inverted axis
+        assert!(plot.fraction(2, 0.3, &labels[2]) > plot.fraction(2, 0.8, &labels[2]));
+    }
+
+    #[test]
+    fn test_plot() {
+        let mut fake_canvas = MockCanvas::new();
+        let mut dims = GraphDimensions::new(600.0, 600.0);
+        dims.min = GraphCoord::new(0.0, 0.0);
+        dims.max = GraphCoord::new(1.0, 1.0);
+
+        let dimensions = ["depth", "width", "accuracy"];
+        let records = [
+            ParallelRecord::new(vec!(2.0, 64.0, 0.91)).category("adam"),
+            ParallelRecord::new(vec!(4.0, f64::NAN, 0.87)).category("sgd"),
+            ParallelRecord::new(vec!(8.0, 256.0, 0.95)).category("adam"),
+        ];
+
+        let plot = ParallelCoordinates::new(&dimensions, &records).color(ParallelColor::Category);
+        assert_eq!(plot.plot(&dims, &mut fake_canvas), Ok(()));
+        assert_eq!(fake_canvas.color, Color(0, 0, 0));
+        assert_eq!(plot.legend_entries(), vec!(LegendEntry::new("adam", PALETTE[0]), LegendEntry::new("sgd", PALETTE[1])));
+
+        let plot = ParallelCoordinates::new(&dimensions, &records).color(ParallelColor::Dimension(2));
+        assert_eq!(plot.plot(&dims, &mut fake_canvas), Ok(()));
+        assert!(plot.legend_entries().is_empty());
+    }
+}
@@ -1,8 +1,10 @@
 use canvas::Canvas;
-use pixel::GraphCoord;
-use options::PointStyle;
+use pixel::{GraphCoord, Pixel};
+use options::{PointStyle, Orientation};
 use graph_dimensions::GraphDimensions;
 use plottable::Plottable;
+use utils;
+use std::f64;
 
 #[derive(Copy, Clone, PartialEq, Debug)]
 pub struct Line(pub GraphCoord, pub GraphCoord);
@@ -19,36 +21,198 @@ impl Plottable for Line {
 pub struct Point(pub GraphCoord, pub PointStyle);
 
 impl Point {
-    fn plot_cross<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
-        let pix = try_opt!(bounds.convert_to_pixel(self.0));
+    /// Draws the marker for `style` centred on `pix`, this is used by
+    /// graphs which convert their own co-ordinates to pixels
+    pub fn draw_marker<C: Canvas>(style: PointStyle, pix: Pixel, canvas: &mut C) -> Result<(), C::Err> {
+        match style {
+            PointStyle::Cross => Point::draw_cross(pix, canvas),
+            PointStyle::Circle => Point::draw_circle(pix, canvas),
+            PointStyle::Nothing => Ok(()),
+        }
+    }
 
+    fn draw_cross<C: Canvas>(pix: Pixel, canvas: &mut C) -> Result<(), C::Err> {
         canvas.draw_line((pix.x + 2.5, pix.y), (pix.x-2.5, pix.y))?;
         canvas.draw_line((pix.x, pix.y + 2.5), (pix.x, pix.y-2.5))
     }
+
+    fn draw_circle<C: Canvas>(pix: Pixel, canvas: &mut C) -> Result<(), C::Err> {
+        let radius = 3.5;
+        let segments = 12;
+
+        let around = |n: i32| {
+            let theta = (n as f64) * 2.0 * f64::consts::PI / (segments as f64);
+            (pix.x + radius * theta.cos(), pix.y + radius * theta.sin())
+        };
+
+        for n in 0..segments {
+            canvas.draw_line(around(n), around(n + 1))?;
+        }
+
+        Ok(())
+    }
 }
 
 impl Plottable for Point {
     fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
-        match self.1 {
-            PointStyle::Cross => self.plot_cross(bounds, canvas),
-            PointStyle::Nothing => Ok(()),
-        }
+        let pix = try_opt!(bounds.convert_to_pixel(self.0));
+        Point::draw_marker(self.1, pix, canvas)
     }
 }
 
-pub struct Bar(pub GraphCoord);
+/// A single bar which spans from `start` to `end` along the value axis
+/// and is `width` wide, centred on `position`
+/// If the orientation is vertical then the value axis is y
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Bar {
+    pub position: f64,
+    pub start: f64,
+    pub end: f64,
+    pub width: f64,
+    pub orientation: Orientation,
+}
+
+impl Bar {
+    pub fn new(position: f64, start: f64, end: f64, width: f64, orientation: Orientation) -> Bar {
+        Bar {
+            position: position,
+            start: start,
+            end: end,
+            width: width,
+            orientation: orientation,
+        }
+    }
+
+    /// The bottom left and top right corners of the bar
+    pub fn corners(&self) -> (GraphCoord, GraphCoord) {
+        let (low, high) = (self.start.min(self.end), self.start.max(self.end));
+        let half_width = self.width / 2.0;
+
+        match self.orientation {
+            Orientation::Vertical => (GraphCoord::new(self.position - half_width, low),
+                                      GraphCoord::new(self.position + half_width, high)),
+            Orientation::Horizontal => (GraphCoord::new(low, self.position - half_width),
+                                        GraphCoord::new(high, self.position + half_width)),
+        }
+    }
+}
 
 impl Plottable for Bar {
     fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
-        let GraphCoord { x, y } = self.0;
-        let bottom_left = try_opt!(bounds.convert_to_pixel((x - 0.5, 0.0)));
-        let top_left = try_opt!(bounds.convert_to_pixel((x - 0.5, y)));
-        let bottom_right = try_opt!(bounds.convert_to_pixel((x + 0.5, 0.0)));
+        let (min, max) = self.corners();
+
+        // clip the bar to the bounds so bars that are partly
+        // off the grid are still drawn
+        let min = GraphCoord::new(min.x.max(bounds.min.x), min.y.max(bounds.min.y));
+        let max = GraphCoord::new(max.x.min(bounds.max.x), max.y.min(bounds.max.y));
+        if min.x >= max.x || min.y >= max.y {
+            return Ok(());
+        }
 
-        let width = bottom_right.x - bottom_left.x;
-        let height = top_left.y - bottom_left.y;
+        let bottom_left = try_opt!(bounds.convert_to_pixel(min));
+        let top_right = try_opt!(bounds.convert_to_pixel(max));
+
+        let width = top_right.x - bottom_left.x;
+        let height = top_right.y - bottom_left.y;
 
         canvas.fill_rect(bottom_left, width, height)
     }
 }
 
+/// A line from the first point to the second point with
+/// an arrow head at the second point
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Arrow(pub GraphCoord, pub GraphCoord);
+
+impl Arrow {
+    /// Draws an arrow between two pixels, the head is `head_size` pixels long
+    /// unless the arrow is too short for it
+    pub fn draw<C: Canvas>(start: Pixel, end: Pixel, head_size: f64, canvas: &mut C) -> Result<(), C::Err> {
+        canvas.draw_line(start, end)?;
+
+        let (dx, dy) = (end.x - start.x, end.y - start.y);
+        let length = (dx * dx + dy * dy).sqrt();
+        if length == 0.0 {
+            return Ok(());
+        }
+
+        let head = head_size.min(length * 0.4);
+        let angle = dy.atan2(dx);
+        let spread = f64::consts::PI / 7.0;
+
+        for &side in &[angle + spread, angle - spread] {
+            let tip = Pixel::new(end.x - head * side.cos(), end.y - head * side.sin());
+            canvas.draw_line(end, tip)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Plottable for Arrow {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let start = try_opt!(bounds.convert_to_pixel(self.0));
+        let end = try_opt!(bounds.convert_to_pixel(self.1));
+
+        Arrow::draw(start, end, 8.0, canvas)
+    }
+}
+
+/// A filled circle, the radius is in pixels so the circle
+/// stays round no matter the scale of the graph
+pub struct Circle(pub GraphCoord, pub f64);
+
+impl Circle {
+    /// The outline of a circle with centre `pix`, made of enough points
+    /// that it looks round
+    pub fn outline(pix: Pixel, radius: f64) -> Vec<Pixel> {
+        let segments = (radius * 2.0).clamp(12.0, 90.0) as i32;
+
+        (0..segments).map(|n| {
+            let theta = (n as f64) * 2.0 * f64::consts::PI / (segments as f64);
+            Pixel::new(pix.x + radius * theta.cos(), pix.y + radius * theta.sin())
+        }).collect()
+    }
+}
+
+impl Plottable for Circle {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let pix = try_opt!(bounds.convert_to_pixel(self.0));
+        canvas.fill_polygon(&Circle::outline(pix, self.1))
+    }
+}
+
+/// A filled polygon, anything outside of the bounds is clipped
+/// before being drawn
+pub struct Polygon(pub Vec<GraphCoord>);
+
+impl Plottable for Polygon {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let clipped = utils::clip_polygon(&self.0, bounds.min, bounds.max);
+        let pixels = clipped.into_iter()
+            .filter_map(|p| bounds.convert_to_pixel(p))
+            .collect::<Vec<_>>();
+
+        canvas.fill_polygon(&pixels)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use options::Orientation;
+    use pixel::GraphCoord;
+
+    #[test]
+    fn test_bar_corners() {
+        let bar = Bar::new(2.0, 0.0, 5.0, 1.0, Orientation::Vertical);
+        assert_eq!(bar.corners(), (GraphCoord::new(1.5, 0.0), GraphCoord::new(2.5, 5.0)));
+
+        // negative bars should hang below the baseline instead of having a negative height
+        let bar = Bar::new(2.0, 1.0, -3.0, 0.5, Orientation::Vertical);
+        assert_eq!(bar.corners(), (GraphCoord::new(1.75, -3.0), GraphCoord::new(2.25, 1.0)));
+
+        let bar = Bar::new(2.0, 0.0, -4.0, 1.0, Orientation::Horizontal);
+        assert_eq!(bar.corners(), (GraphCoord::new(-4.0, 1.5), GraphCoord::new(0.0, 2.5)));
+    }
+}
@@ -0,0 +1,202 @@
+use plottable::Plottable;
+use plottable::primitives::Arrow;
+use colormap::Colormap;
+use graph_dimensions::GraphDimensions;
+use canvas::Canvas;
+use pixel::{Color, GraphCoord, Pixel};
+use utils;
+use std::f64;
+
+/// `QuiverSeries` draws an arrow for the vector (u, v) at each position
+/// Unless a scale is given the arrows are scaled so the longest arrow is
+/// about as long as the average gap between positions
+pub struct QuiverSeries<'a> {
+    positions: &'a [GraphCoord],
+    vectors: &'a [(f64, f64)],
+    scale: Option<f64>,
+    color: Color,
+    colormap: Option<Colormap>,
+    key: Option<(f64, &'a str)>,
+}
+
+impl <'a> QuiverSeries<'a> {
+    /// Creates a new `QuiverSeries`, returns `None` unless there
+    /// is exactly one vector for each position
+    pub fn new(positions: &'a [GraphCoord], vectors: &'a [(f64, f64)]) -> Option<Self> {
+        if positions.len() != vectors.len() {
+            return None;
+        }
+
+        Some(QuiverSeries {
+            positions: positions,
+            vectors: vectors,
+            scale: None,
+            color: Color(0, 0, 0),
+            colormap: None,
+            key: None,
+        })
+    }
+
+    /// Multiplies each vector by `scale` to get the length of its arrow
+    pub fn scale<S: Into<Option<f64>>>(mut self, scale: S) -> Self {
+        self.scale = scale.into();
+        self
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Colours each arrow by its magnitude instead of using one colour
+    pub fn colormap<C: Into<Option<Colormap>>>(mut self, colormap: C) -> Self {
+        self.colormap = colormap.into();
+        self
+    }
+
+    /// Draws a reference arrow of the given magnitude with a label
+    /// in the top left of the graph
+    pub fn key<K: Into<Option<(f64, &'a str)>>>(mut self, key: K) -> Self {
+        self.key = key.into();
+        self
+    }
+
+    fn magnitudes(&self) -> Vec<f64> {
+        self.vectors.iter().map(|&(u, v)| (u * u + v * v).sqrt()).collect()
+    }
+
+    /// The number each vector is multiplied by to get its arrow
+    pub fn get_scale(&self) -> f64 {
+        if let Some(scale) = self.scale {
+            return scale;
+        }
+
+        let max_magnitude = self.magnitudes().into_iter()
+            .filter(|m| m.is_finite())
+            .fold(0.0, |acc: f64, m| acc.max(m));
+        if max_magnitude == 0.0 {
+            return 1.0;
+        }
+
+        // the average gap if the positions were spread evenly over their bounding box
+        let (min, max) = (utils::get_min_coord(self.positions), utils::get_max_coord(self.positions));
+        let (width, height) = (max.x - min.x, max.y - min.y);
+        let count = self.positions.len() as f64;
+        let gap = if width > 0.0 && height > 0.0 {
+            (width * height / count).sqrt()
+        } else if width.max(height) > 0.0 {
+            width.max(height) / count
+        } else {
+            1.0
+        };
+
+        0.9 * gap / max_magnitude
+    }
+
+    /// The start and end of each arrow
+    pub fn arrows(&self) -> Vec<Arrow> {
+        let scale = self.get_scale();
+
+        self.positions.iter().zip(self.vectors.iter())
+            .map(|(&p, &(u, v))| Arrow(p, GraphCoord::new(p.x + scale * u, p.y + scale * v)))
+            .collect()
+    }
+
+    fn plot_key<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let (magnitude, label) = match self.key {
+            Some(key) => key,
+            None => return Ok(()),
+        };
+
+        let (bottom_left, top_right) = bounds.plot_area();
+        let pixels_per_unit = (top_right.x - bottom_left.x) / (bounds.max.x - bounds.min.x);
+        let length = magnitude * self.get_scale() * pixels_per_unit;
+
+        let start = Pixel::new(bottom_left.x + bounds.width * 0.02, top_right.y - bounds.height * 0.03);
+        let end = Pixel::new(start.x + length, start.y);
+
+        canvas.set_color(Color(0, 0, 0));
+        Arrow::draw(start, end, 8.0, canvas)?;
+        canvas.write_text(label, (end.x + 5.0, start.y - 6.0))
+    }
+}
+
+impl <'a> Plottable for QuiverSeries<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let magnitudes = self.magnitudes();
+        let finite = magnitudes.iter().cloned().filter(|m| m.is_finite());
+        let (min, max) = finite.fold((f64::MAX, f64::MIN), |(min, max), m| (min.min(m), max.max(m)));
+
+        canvas.set_color(self.color);
+        for (arrow, &magnitude) in self.arrows().iter().zip(magnitudes.iter()) {
+            if let Some(colormap) = self.colormap {
+                canvas.set_color(colormap.color_for(magnitude, min, max));
+            }
+            arrow.plot(bounds, canvas)?;
+        }
+
+        self.plot_key(bounds, canvas)
+    }
+
+    fn data_extent(&self) -> Option<(GraphCoord, GraphCoord)> {
+        if self.positions.is_empty() {
+            return None;
+        }
+
+        let ends = self.arrows().iter()
+            .flat_map(|&Arrow(start, end)| vec!(start, end))
+            .filter(|p| p.x.is_finite() && p.y.is_finite())
+            .collect::<Vec<_>>();
+
+        Some((utils::get_min_coord(&ends), utils::get_max_coord(&ends)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::mock_canvas::MockCanvas;
+    use colormap::Colormap;
+    use graph_dimensions::GraphDimensions;
+    use pixel::GraphCoord;
+    use plottable::Plottable;
+
+    #[test]
+    fn test_scale() {
+        let positions = [GraphCoord::new(0.0, 0.0), GraphCoord::new(2.0, 0.0),
+                         GraphCoord::new(0.0, 2.0), GraphCoord::new(2.0, 2.0)];
+        let vectors = [(1.0, 0.0), (0.0, 2.0), (-1.0, 0.0), (0.0, 0.0)];
+
+        // the positions cover a 2 by 2 box so the average gap is 1
+        // and the longest vector is 2
+        let quiver = QuiverSeries::new(&positions, &vectors).unwrap();
+        assert_eq!(quiver.get_scale(), 0.45);
+        assert_eq!(quiver.data_extent(), Some((GraphCoord::new(-0.45, 0.0), GraphCoord::new(2.0, 2.0))));
+
+        let quiver = quiver.scale(1.0);
+        assert_eq!(quiver.arrows()[1], Arrow(GraphCoord::new(2.0, 0.0), GraphCoord::new(2.0, 2.0)));
+
+        // there must be one vector for each position
+        assert!(QuiverSeries::new(&positions, &vectors[..3]).is_none());
+    }
+
+    #[test]
+    fn test_plot() {
+        let mut fake_canvas = MockCanvas::new();
+        let positions = [GraphCoord::new(0.0, 0.0), GraphCoord::new(1.0, 1.0)];
+        let vectors = [(1.0, 0.0), (0.0, 3.0)];
+
+        let quiver = QuiverSeries::new(&positions, &vectors).unwrap()
+            .colormap(Colormap::Heat)
+            .key((1.0, "1 m/s"));
+        let (min, max) = quiver.data_extent().unwrap();
+
+        let mut dims = GraphDimensions::new(600.0, 600.0);
+        dims.min = min;
+        dims.max = max;
+
+        assert_eq!(quiver.plot(&dims, &mut fake_canvas), Ok(()));
+        // the key is always drawn last in black
+        assert_eq!(fake_canvas.color, Color(0, 0, 0));
+    }
+}
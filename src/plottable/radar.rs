@@ -0,0 +1,250 @@
+use plottable::{Plottable, LegendEntry};
+use labeller::{Labeller, Label};
+use graph_dimensions::GraphDimensions;
+use canvas::Canvas;
+use pixel::{Color, GraphCoord, Pixel};
+use std::f64;
+use std::f64::consts::PI;
+
+/// `RadarRecord` is a single named record on a `RadarChart`
+/// with one value for each axis
+#[derive(Clone, PartialEq, Debug)]
+pub struct RadarRecord<'a> {
+    pub name: &'a str,
+    pub values: Vec<f64>,
+    pub color: Color,
+}
+
+impl <'a> RadarRecord<'a> {
+    pub fn new(name: &'a str, values: Vec<f64>, color: Color) -> RadarRecord<'a> {
+        RadarRecord { name: name, values: values, color: color }
+    }
+}
+
+/// `RadarChart` draws a spoke for each named axis, each with its own scale,
+/// and each record as a translucent polygon joining its value on every spoke
+/// It lays itself out in the middle of the graph so it should be added to a
+/// `Graph2D` without any `AxisOptions`
+pub struct RadarChart<'a> {
+    axes: &'a [&'a str],
+    records: &'a [RadarRecord<'a>],
+    ranges: Vec<Option<(f64, f64)>>,
+    ring_count: i32,
+    alpha: u8,
+}
+
+impl <'a> RadarChart<'a> {
+    pub fn new(axes: &'a [&'a str], records: &'a [RadarRecord<'a>]) -> Self {
+        RadarChart {
+            axes: axes,
+            records: records,
+            ranges: vec!(None; axes.len()),
+            ring_count: 4,
+            alpha: 80,
+        }
+    }
+
+    /// Sets the scale of a single axis instead of choosing it from the records
+    pub fn axis_range(mut self, axis: usize, min: f64, max: f64) -> Self {
+        if axis < self.ranges.len() {
+            self.ranges[axis] = Some((min, max));
+        }
+        self
+    }
+
+    /// The amount of rings the `Labeller` should aim for on each axis
+    pub fn ring_count(mut self, ring_count: i32) -> Self {
+        self.ring_count = ring_count;
+        self
+    }
+
+    /// The opacity of the polygon filled in for each record
+    pub fn alpha(mut self, alpha: u8) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    /// The scale of each axis, unless it has been given this runs from zero,
+    /// or the smallest value, in steps chosen by the `Labeller`
+    /// Every scale has the same amount of steps so each ring of the grid
+    /// is at a step on every axis
+    pub fn scales(&self) -> Vec<Label> {
+        let mut labeller = Labeller::in_base10();
+        labeller.loose = true;
+
+        let automatic = self.ranges.iter().enumerate().map(|(n, range)| {
+            if range.is_some() {
+                return None;
+            }
+
+            let values = self.records.iter().filter_map(|r| r.values.get(n)).filter(|v| v.is_finite());
+            let (min, max) = values.fold((0.0, f64::MIN), |(min, max): (f64, f64), &v| (min.min(v), max.max(v)));
+            let label = labeller.search(min, if max <= min { min + 1.0 } else { max }, self.ring_count);
+            if label.step > 0.0 { Some(label) } else { None }
+        }).collect::<Vec<_>>();
+
+        let steps = automatic.iter()
+            .filter_map(|label| label.as_ref())
+            .map(|label| ((label.max - label.min) / label.step).round() as i32)
+            .max()
+            .unwrap_or(self.ring_count)
+            .max(1);
+
+        self.ranges.iter().zip(automatic).map(|(range, label)| {
+            // an empty range is widened so every value still has a place on the spoke
+            let range = range.map(|(min, max)| if max <= min { (min - 1.0, min + 1.0) } else { (min, max) });
+            let (min, max, step) = match (range, label) {
+                (Some((min, max)), _) => (min, max, (max - min) / (steps as f64)),
+                (None, Some(label)) => (label.min, label.min + label.step * (steps as f64), label.step),
+                (None, None) => (0.0, 1.0, 1.0 / (steps as f64)),
+            };
+            Label { min: min, max: max, step: step, score: 0.0 }
+        }).collect()
+    }
+
+    /// The minimum and maximum of the scale on each axis
+    pub fn ranges(&self) -> Vec<(f64, f64)> {
+        self.scales().iter().map(|label| (label.min, label.max)).collect()
+    }
+
+    // how far along a spoke `value` is, from 0 at the centre to 1 at the end
+    fn fraction(value: f64, (min, max): (f64, f64)) -> f64 {
+        ((value - min) / (max - min)).clamp(0.0, 1.0)
+    }
+
+    fn spoke_angle(&self, n: usize) -> f64 {
+        // start with the first spoke pointing up and go clockwise
+        PI / 2.0 - 2.0 * PI * (n as f64) / (self.axes.len() as f64)
+    }
+}
+
+impl <'a> Plottable for RadarChart<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        if self.axes.len() < 3 {
+            return Ok(());
+        }
+
+        let (bottom_left, top_right) = bounds.plot_area();
+        let centre = Pixel::new((bottom_left.x + top_right.x) / 2.0, (bottom_left.y + top_right.y) / 2.0);
+        let radius = 0.4 * (top_right.x - bottom_left.x).min(top_right.y - bottom_left.y);
+
+        let at = |n: usize, fraction: f64| {
+            let angle = self.spoke_angle(n);
+            Pixel::new(centre.x + radius * fraction * angle.cos(), centre.y + radius * fraction * angle.sin())
+        };
+
+        let scales = self.scales();
+        let ranges = scales.iter().map(|label| (label.min, label.max)).collect::<Vec<_>>();
+        let rings = scales.first().map_or(0, |label| ((label.max - label.min) / label.step).round() as i32);
+
+        // the grid rings are polygons at each step of the scales
+        canvas.set_color(Color(0, 0, 0));
+        for ring in 1..(rings + 1) {
+            let fraction = (ring as f64) / (rings as f64);
+            for n in 0..self.axes.len() {
+                canvas.draw_line(at(n, fraction), at((n + 1) % self.axes.len(), fraction))?;
+            }
+        }
+
+        // each scale is only labelled once, on the first spoke that uses it
+        for (n, label) in scales.iter().enumerate() {
+            if scales[..n].iter().any(|other| other.min == label.min && other.step == label.step) {
+                continue;
+            }
+
+            for ring in 1..(rings + 1) {
+                let fraction = (ring as f64) / (rings as f64);
+                canvas.write_num_centred(label.min + label.step * (ring as f64), at(n, fraction))?;
+            }
+        }
+
+        for (n, name) in self.axes.iter().enumerate() {
+            canvas.draw_line(centre, at(n, 1.0))?;
+            canvas.write_text_centred(name, at(n, 1.15))?;
+        }
+
+        for record in self.records.iter() {
+            let outline = (0..self.axes.len())
+                .map(|n| at(n, RadarChart::fraction(record.values.get(n).cloned().unwrap_or(0.0), ranges[n])))
+                .collect::<Vec<_>>();
+
+            canvas.set_color(record.color);
+            canvas.set_alpha(self.alpha);
+            let res = canvas.fill_polygon(&outline);
+            canvas.set_alpha(255);
+            res?;
+
+            for n in 0..outline.len() {
+                canvas.draw_line(outline[n], outline[(n + 1) % outline.len()])?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn data_extent(&self) -> Option<(GraphCoord, GraphCoord)> {
+        Some((GraphCoord::new(0.0, 0.0), GraphCoord::new(1.0, 1.0)))
+    }
+
+    fn legend_entries(&self) -> Vec<LegendEntry<'_>> {
+        self.records.iter().map(|r| LegendEntry::new(r.name, r.color)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::mock_canvas::MockCanvas;
+    use graph_dimensions::GraphDimensions;
+    use pixel::{Color, GraphCoord};
+    use plottable::Plottable;
+
+    #[test]
+    fn test_ranges() {
+        let axes = ["size", "speed", "memory"];
+        let records = [
+            RadarRecord::new("debug", vec!(12.0, 3.0, 40.0), Color(255, 0, 0)),
+            RadarRecord::new("release", vec!(4.0, 9.0, 35.0), Color(0, 0, 255)),
+        ];
+
+        let chart = RadarChart::new(&axes, &records).axis_range(1, 0.0, 10.0);
+        let ranges = chart.ranges();
+
+        // every automatic range should start at zero and cover every record
+        assert!(ranges[0].0 == 0.0 && ranges[0].1 >= 12.0);
+        assert_eq!(ranges[1], (0.0, 10.0));
+        assert!(ranges[2].0 == 0.0 && ranges[2].1 >= 40.0);
+
+        // every scale has the same amount of steps so the rings line up
+        let scales = chart.scales();
+        for label in scales.iter() {
+            assert_eq!(((label.max - label.min) / label.step).round(), 4.0);
+        }
+        assert_eq!(scales[1].step, 2.5);
+
+        // an empty range should be widened rather than divide by zero
+        let chart = RadarChart::new(&axes, &records).axis_range(1, 5.0, 5.0);
+        assert_eq!(chart.ranges()[1], (4.0, 6.0));
+        assert_eq!(RadarChart::fraction(5.0, chart.ranges()[1]), 0.5);
+
+        assert_eq!(RadarChart::fraction(5.0, (0.0, 10.0)), 0.5);
+        // values off the scale should be kept on the chart
+        assert_eq!(RadarChart::fraction(15.0, (0.0, 10.0)), 1.0);
+    }
+
+    #[test]
+    fn test_plot() {
+        let mut fake_canvas = MockCanvas::new();
+        let mut dims = GraphDimensions::new(600.0, 600.0);
+        dims.min = GraphCoord::new(0.0, 0.0);
+        dims.max = GraphCoord::new(1.0, 1.0);
+
+        let axes = ["size", "speed", "memory", "build time"];
+        let records = [RadarRecord::new("opt-level=3", vec!(1.0, 2.0, 3.0, 4.0), Color(255, 0, 0))];
+        let chart = RadarChart::new(&axes, &records);
+
+        assert_eq!(chart.plot(&dims, &mut fake_canvas), Ok(()));
+        assert_eq!(fake_canvas.alpha, 255);
+        assert_eq!(chart.legend_entries(), vec!(LegendEntry::new("opt-level=3", Color(255, 0, 0))));
+    }
+}
@@ -0,0 +1,366 @@
+use plottable::Plottable;
+use colormap::PALETTE;
+use graph_dimensions::GraphDimensions;
+use canvas::Canvas;
+use pixel::{Color, GraphCoord, Pixel};
+use utils;
+use std::cmp::Ordering;
+use std::f64;
+
+// The amount of straight lines each edge of a band is drawn with
+const CURVE_SEGMENTS: usize = 24;
+
+/// A node of a `Sankey` diagram
+#[derive(Clone, PartialEq, Debug)]
+pub struct SankeyNode<'a> {
+    pub name: &'a str,
+    pub color: Option<Color>,
+}
+
+impl <'a> SankeyNode<'a> {
+    pub fn new(name: &'a str) -> SankeyNode<'a> {
+        SankeyNode { name: name, color: None }
+    }
+
+    /// Draws the node, and every band leaving it, in `color`
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+/// A flow of `value` from the node at index `source` to the node at index `target`
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SankeyLink {
+    pub source: usize,
+    pub target: usize,
+    pub value: f64,
+}
+
+impl SankeyLink {
+    pub fn new(source: usize, target: usize, value: f64) -> SankeyLink {
+        SankeyLink { source: source, target: target, value: value }
+    }
+}
+
+/// The rectangle laid out for a node of a `Sankey` diagram, in pixels
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct NodeBox<'a> {
+    pub name: &'a str,
+    pub column: usize,
+    pub bottom_left: Pixel,
+    pub top_right: Pixel,
+    pub color: Color,
+}
+
+/// The band laid out for a link of a `Sankey` diagram, in pixels, it runs from
+/// the right side of the source node to the left side of the target node
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Band {
+    pub link: usize,
+    pub start: Pixel,
+    pub end: Pixel,
+    pub width: f64,
+    pub color: Color,
+}
+
+impl Band {
+    /// The outline of the band, along the top curve then back along the bottom
+    pub fn outline(&self) -> Vec<Pixel> {
+        let curve = |start: Pixel, end: Pixel| {
+            let middle = (start.x + end.x) / 2.0;
+            utils::cubic_bezier(start, Pixel::new(middle, start.y), Pixel::new(middle, end.y), end, CURVE_SEGMENTS)
+        };
+
+        let mut outline = curve(self.start, self.end);
+        let below = |p: Pixel| Pixel::new(p.x, p.y - self.width);
+        outline.extend(curve(below(self.start), below(self.end)).into_iter().rev());
+        outline
+    }
+}
+
+/// `Sankey` draws the flow between nodes, each node is put in a column after
+/// every node that flows into it and each link is a band with a width in
+/// proportion to its value
+/// It lays itself out inside the graph so it should be drawn without an `Axis`
+pub struct Sankey<'a> {
+    nodes: &'a [SankeyNode<'a>],
+    links: &'a [SankeyLink],
+    node_width: f64,
+    node_padding: f64,
+    alpha: u8,
+}
+
+impl <'a> Sankey<'a> {
+    pub fn new(nodes: &'a [SankeyNode<'a>], links: &'a [SankeyLink]) -> Self {
+        Sankey {
+            nodes: nodes,
+            links: links,
+            node_width: 15.0,
+            node_padding: 10.0,
+            alpha: 120,
+        }
+    }
+
+    /// The width of each node in pixels
+    pub fn node_width(mut self, node_width: f64) -> Self {
+        self.node_width = node_width.max(0.0);
+        self
+    }
+
+    /// The gap in pixels between each node in the same column
+    pub fn node_padding(mut self, node_padding: f64) -> Self {
+        self.node_padding = node_padding.max(0.0);
+        self
+    }
+
+    /// The opacity of the bands
+    pub fn alpha(mut self, alpha: u8) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    // Links that join two different nodes with a positive value, anything else can't be drawn
+    fn valid_links(&self) -> Vec<(usize, SankeyLink)> {
+        self.links.iter().cloned().enumerate()
+            .filter(|&(_, l)| l.source < self.nodes.len() && l.target < self.nodes.len())
+            .filter(|&(_, l)| l.source != l.target && l.value > 0.0 && l.value.is_finite())
+            .collect()
+    }
+
+    /// The column of each node, which is one more than the furthest column of
+    /// anything flowing into it, nodes with nothing flowing out go in the last column
+    pub fn columns(&self) -> Vec<usize> {
+        let links = self.valid_links();
+        let n = self.nodes.len();
+        let edges = links.iter().map(|&(_, l)| (l.source, l.target)).collect::<Vec<_>>();
+        let mut columns = utils::longest_path_layers(n, &edges);
+
+        let last = columns.iter().cloned().max().unwrap_or(0);
+        for (node, column) in columns.iter_mut().enumerate() {
+            let has_input = links.iter().any(|&(_, l)| l.target == node);
+            let has_output = links.iter().any(|&(_, l)| l.source == node);
+            if has_input && !has_output {
+                *column = last;
+            }
+        }
+
+        columns
+    }
+
+    /// The value of each node, the larger of everything flowing in and everything flowing out
+    pub fn node_values(&self) -> Vec<f64> {
+        let mut inputs = vec!(0.0; self.nodes.len());
+        let mut outputs = vec!(0.0; self.nodes.len());
+        for (_, link) in self.valid_links() {
+            outputs[link.source] += link.value;
+            inputs[link.target] += link.value;
+        }
+
+        inputs.into_iter().zip(outputs).map(|(i, o): (f64, f64)| i.max(o)).collect()
+    }
+
+    /// Lays out a box for each node and a band for each link inside the graph
+    pub fn layout(&self, bounds: &GraphDimensions) -> (Vec<NodeBox<'a>>, Vec<Band>) {
+        let (bottom_left, top_right) = bounds.plot_area();
+        let (width, height) = (top_right.x - bottom_left.x, top_right.y - bottom_left.y);
+
+        let columns = self.columns();
+        let values = self.node_values();
+        let column_count = columns.iter().cloned().max().map_or(0, |c| c + 1);
+
+        // every column has the same scale, the fullest column decides it
+        let scale = (0..column_count).map(|c| {
+            let nodes = (0..self.nodes.len()).filter(|&n| columns[n] == c).collect::<Vec<_>>();
+            let total = nodes.iter().map(|&n| values[n]).sum::<f64>();
+            let space = height - self.node_padding * (nodes.len().saturating_sub(1) as f64);
+            if total > 0.0 { space / total } else { f64::MAX }
+        }).fold(f64::MAX, f64::min);
+
+        if !(scale > 0.0 && scale < f64::MAX) {
+            return (vec!(), vec!());
+        }
+
+        let column_x = |c: usize| {
+            if column_count > 1 {
+                bottom_left.x + (width - self.node_width) * (c as f64) / ((column_count - 1) as f64)
+            } else {
+                bottom_left.x
+            }
+        };
+
+        // nodes are stacked from the top of each column in the order they were given
+        let mut boxes = Vec::with_capacity(self.nodes.len());
+        let mut tops = vec!(0.0; self.nodes.len());
+        for c in 0..column_count {
+            let nodes = (0..self.nodes.len()).filter(|&n| columns[n] == c).collect::<Vec<_>>();
+            let used = nodes.iter().map(|&n| values[n] * scale).sum::<f64>() +
+                self.node_padding * (nodes.len().saturating_sub(1) as f64);
+
+            let mut top = top_right.y - (height - used) / 2.0;
+            for &n in nodes.iter() {
+                tops[n] = top;
+                top -= values[n] * scale + self.node_padding;
+            }
+        }
+
+        for (n, node) in self.nodes.iter().enumerate() {
+            let x = column_x(columns[n]);
+            boxes.push(NodeBox {
+                name: node.name,
+                column: columns[n],
+                bottom_left: Pixel::new(x, tops[n] - values[n] * scale),
+                top_right: Pixel::new(x + self.node_width, tops[n]),
+                color: node.color.unwrap_or(PALETTE[n % PALETTE.len()]),
+            });
+        }
+
+        // bands leave each node in the order of where they go, from the top down,
+        // and arrive in the order of where they came from, so they don't cross
+        let centre = |n: usize| (boxes[n].bottom_left.y + boxes[n].top_right.y) / 2.0;
+        let by_centre = |a: usize, b: usize| centre(b).partial_cmp(&centre(a)).unwrap_or(Ordering::Equal);
+
+        let mut outgoing = self.valid_links();
+        outgoing.sort_by(|a, b| by_centre(a.1.target, b.1.target));
+        let mut incoming = self.valid_links();
+        incoming.sort_by(|a, b| by_centre(a.1.source, b.1.source));
+
+        let mut start_offsets = vec!(0.0; self.nodes.len());
+        let mut starts = vec!(0.0; self.links.len());
+        for &(i, link) in outgoing.iter() {
+            starts[i] = tops[link.source] - start_offsets[link.source];
+            start_offsets[link.source] += link.value * scale;
+        }
+
+        let mut end_offsets = vec!(0.0; self.nodes.len());
+        let mut bands = Vec::with_capacity(self.links.len());
+        for &(i, link) in incoming.iter() {
+            let end = tops[link.target] - end_offsets[link.target];
+            end_offsets[link.target] += link.value * scale;
+
+            bands.push(Band {
+                link: i,
+                start: Pixel::new(boxes[link.source].top_right.x, starts[i]),
+                end: Pixel::new(boxes[link.target].bottom_left.x, end),
+                width: link.value * scale,
+                color: boxes[link.source].color,
+            });
+        }
+        bands.sort_by_key(|b| b.link);
+
+        (boxes, bands)
+    }
+}
+
+impl <'a> Plottable for Sankey<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let (boxes, bands) = self.layout(bounds);
+
+        for band in bands.iter() {
+            canvas.set_color(band.color);
+            canvas.set_alpha(self.alpha);
+            let res = canvas.fill_polygon(&band.outline());
+            canvas.set_alpha(255);
+            res?;
+        }
+
+        let last_column = boxes.iter().map(|b| b.column).max().unwrap_or(0);
+        for node in boxes.iter() {
+            let height = node.top_right.y - node.bottom_left.y;
+            canvas.set_color(node.color);
+            canvas.fill_rect(node.bottom_left, self.node_width, height)?;
+
+            // labels go to the right of each node, apart from the
+            // last column where there is only room on the left
+            let (text_width, text_height) = canvas.text_size(node.name)?;
+            let y = node.bottom_left.y + (height - text_height) / 2.0;
+            let x = if node.column == last_column && last_column > 0 {
+                node.bottom_left.x - 4.0 - text_width
+            } else {
+                node.top_right.x + 4.0
+            };
+
+            canvas.set_color(Color(0, 0, 0));
+            canvas.write_text(node.name, (x, y))?;
+        }
+
+        Ok(())
+    }
+
+    fn data_extent(&self) -> Option<(GraphCoord, GraphCoord)> {
+        Some((GraphCoord::new(0.0, 0.0), GraphCoord::new(1.0, 1.0)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::mock_canvas::MockCanvas;
+    use graph_dimensions::GraphDimensions;
+    use pixel::{Color, GraphCoord};
+    use plottable::Plottable;
+
+    fn dims() -> GraphDimensions {
+        let mut dims = GraphDimensions::new(600.0, 600.0);
+        dims.min = GraphCoord::new(0.0, 0.0);
+        dims.max = GraphCoord::new(1.0, 1.0);
+        dims
+    }
+
+    #[test]
+    fn test_columns() {
+        let nodes = [SankeyNode::new("salary"), SankeyNode::new("budget"), SankeyNode::new("rent"),
+                     SankeyNode::new("savings"), SankeyNode::new("bonus")];
+        let links = [SankeyLink::new(0, 1, 3000.0), SankeyLink::new(1, 2, 1200.0), SankeyLink::new(1, 3, 1800.0),
+                     SankeyLink::new(4, 3, 500.0), SankeyLink::new(2, 2, 10.0), SankeyLink::new(0, 9, 1.0)];
+        let sankey = Sankey::new(&nodes, &links);
+
+        // the bonus goes straight into savings which is still in the last column
+        assert_eq!(sankey.columns(), vec!(0, 1, 2, 2, 0));
+        assert_eq!(sankey.node_values(), vec!(3000.0, 3000.0, 1200.0, 2300.0, 500.0));
+
+        // a cycle shouldn't push nodes along forever
+        let cycle = [SankeyLink::new(0, 1, 1.0), SankeyLink::new(1, 0, 1.0)];
+        assert!(Sankey::new(&nodes[..2], &cycle).columns().iter().all(|&c| c < 2));
+    }
+
+    #[test]
+    fn test_layout() {
+        let nodes = [SankeyNode::new("in"), SankeyNode::new("a").color(Color(255, 0, 0)), SankeyNode::new("b")];
+        let links = [SankeyLink::new(0, 1, 3.0), SankeyLink::new(0, 2, 1.0)];
+        let sankey = Sankey::new(&nodes, &links).node_padding(20.0);
+
+        let (boxes, bands) = sankey.layout(&dims());
+        let (bottom_left, top_right) = dims().plot_area();
+        let height = top_right.y - bottom_left.y;
+
+        // the second column is the fullest so it fills the whole height
+        assert!((boxes[1].top_right.y - top_right.y).abs() < 1e-9);
+        assert!((boxes[2].bottom_left.y - bottom_left.y).abs() < 1e-9);
+        assert!((boxes[1].top_right.y - boxes[1].bottom_left.y - 0.75 * (height - 20.0)).abs() < 1e-9);
+        assert_eq!(boxes[1].color, Color(255, 0, 0));
+
+        // both bands leave the source one after the other, the one to the top node first
+        assert_eq!(bands.len(), 2);
+        assert_eq!(bands[0].start.y, boxes[0].top_right.y);
+        assert!((bands[1].start.y - (bands[0].start.y - bands[0].width)).abs() < 1e-9);
+        assert_eq!(bands[1].end.y, boxes[2].top_right.y);
+
+        let outline = bands[0].outline();
+        assert_eq!(outline.len(), 2 * (CURVE_SEGMENTS + 1));
+        assert_eq!(outline[0], bands[0].start);
+    }
+
+    #[test]
+    fn test_plot() {
+        let mut fake_canvas = MockCanvas::new();
+        let nodes = [SankeyNode::new("gateway"), SankeyNode::new("api"), SankeyNode::new("static")];
+        let links = [SankeyLink::new(0, 1, 80.0), SankeyLink::new(0, 2, 20.0)];
+
+        assert_eq!(Sankey::new(&nodes, &links).plot(&dims(), &mut fake_canvas), Ok(()));
+        assert_eq!(fake_canvas.alpha, 255);
+
+        // nothing to lay out without any flow
+        assert_eq!(Sankey::new(&nodes, &[]).layout(&dims()), (vec!(), vec!()));
+    }
+}
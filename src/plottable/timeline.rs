@@ -0,0 +1,249 @@
+use options::Orientation;
+use plottable::{Plottable, Category};
+use plottable::primitives::{Arrow, Bar, Line};
+use graph_dimensions::GraphDimensions;
+use canvas::Canvas;
+use pixel::{Color, GraphCoord, Pixel};
+use std::f64;
+
+/// A task on a `TimelineSeries` which runs from `start` to `end` in `row`
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Task<'a> {
+    pub name: &'a str,
+    pub start: f64,
+    pub end: f64,
+    pub row: usize,
+    pub color: Option<Color>,
+}
+
+impl <'a> Task<'a> {
+    pub fn new(name: &'a str, start: f64, end: f64, row: usize) -> Task<'a> {
+        Task { name: name, start: start, end: end, row: row, color: None }
+    }
+
+    /// Draws this task in `color` instead of the colour of the `TimelineSeries`
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+}
+
+/// A single point in time on a `TimelineSeries`, drawn as a diamond
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Milestone<'a> {
+    pub name: &'a str,
+    pub time: f64,
+    pub row: usize,
+}
+
+impl <'a> Milestone<'a> {
+    pub fn new(name: &'a str, time: f64, row: usize) -> Milestone<'a> {
+        Milestone { name: name, time: time, row: row }
+    }
+}
+
+/// `TimelineSeries` draws each `Task` as a labelled horizontal bar with time along
+/// the x axis, each row is named and the first row is at the top of the graph
+/// The rows are categories on the y axis, which a `Graph2D` labels with their names
+pub struct TimelineSeries<'a> {
+    rows: &'a [&'a str],
+    tasks: &'a [Task<'a>],
+    milestones: &'a [Milestone<'a>],
+    dependencies: &'a [(usize, usize)],
+    now: Option<f64>,
+    color: Color,
+    bar_height: f64,
+}
+
+impl <'a> TimelineSeries<'a> {
+    pub fn new(rows: &'a [&'a str], tasks: &'a [Task<'a>]) -> Self {
+        TimelineSeries {
+            rows: rows,
+            tasks: tasks,
+            milestones: &[],
+            dependencies: &[],
+            now: None,
+            color: Color(60, 90, 180),
+            bar_height: 0.6,
+        }
+    }
+
+    pub fn milestones(mut self, milestones: &'a [Milestone<'a>]) -> Self {
+        self.milestones = milestones;
+        self
+    }
+
+    /// Draws an arrow from the end of one task to the start of another
+    /// each dependency is a pair of indexes into the tasks
+    pub fn dependencies(mut self, dependencies: &'a [(usize, usize)]) -> Self {
+        self.dependencies = dependencies;
+        self
+    }
+
+    /// Draws a line across every row at the time `now`
+    pub fn now<T: Into<Option<f64>>>(mut self, now: T) -> Self {
+        self.now = now.into();
+        self
+    }
+
+    /// The colour of every task without its own colour
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// The height of each bar, a height of 1 leaves no gap between rows
+    pub fn bar_height(mut self, bar_height: f64) -> Self {
+        self.bar_height = bar_height;
+        self
+    }
+
+    /// The y value of the middle of `row`
+    pub fn row_position(&self, row: usize) -> f64 {
+        (self.rows.len() as f64) - 1.0 - (row as f64)
+    }
+
+    /// The bar drawn for each task
+    pub fn bars(&self) -> Vec<Bar> {
+        self.tasks.iter()
+            .map(|t| Bar::new(self.row_position(t.row), t.start, t.end, self.bar_height, Orientation::Horizontal))
+            .collect()
+    }
+
+    fn plot_milestones<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let size = 6.0;
+
+        for milestone in self.milestones.iter() {
+            let pix = match bounds.convert_to_pixel((milestone.time, self.row_position(milestone.row))) {
+                Some(pix) => pix,
+                None => continue,
+            };
+
+            let diamond = [Pixel::new(pix.x, pix.y + size), Pixel::new(pix.x + size, pix.y),
+                           Pixel::new(pix.x, pix.y - size), Pixel::new(pix.x - size, pix.y)];
+            canvas.set_color(Color(0, 0, 0));
+            canvas.fill_polygon(&diamond)?;
+            canvas.write_text(milestone.name, (pix.x + size + 3.0, pix.y - size))?;
+        }
+
+        Ok(())
+    }
+
+    fn plot_dependencies<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        canvas.set_color(Color(80, 80, 80));
+
+        for &(from, to) in self.dependencies.iter() {
+            if let (Some(from), Some(to)) = (self.tasks.get(from), self.tasks.get(to)) {
+                let start = GraphCoord::new(from.end, self.row_position(from.row));
+                let end = GraphCoord::new(to.start, self.row_position(to.row));
+                Arrow(start, end).plot(bounds, canvas)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl <'a> Plottable for TimelineSeries<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        for (task, bar) in self.tasks.iter().zip(self.bars()) {
+            canvas.set_color(task.color.unwrap_or(self.color));
+            bar.plot(bounds, canvas)?;
+
+            let middle = GraphCoord::new((task.start + task.end) / 2.0, self.row_position(task.row));
+            if let Some(pix) = bounds.convert_to_pixel(middle) {
+                canvas.set_color(Color(255, 255, 255));
+                canvas.write_text_centred(task.name, pix)?;
+            }
+        }
+
+        self.plot_dependencies(bounds, canvas)?;
+        self.plot_milestones(bounds, canvas)?;
+
+        if let Some(now) = self.now {
+            canvas.set_color(Color(220, 0, 0));
+            Line(GraphCoord::new(now, bounds.min.y), GraphCoord::new(now, bounds.max.y)).plot(bounds, canvas)?;
+        }
+
+        Ok(())
+    }
+
+    fn data_extent(&self) -> Option<(GraphCoord, GraphCoord)> {
+        if self.rows.is_empty() {
+            return None;
+        }
+
+        let times = self.tasks.iter().flat_map(|t| vec!(t.start, t.end))
+            .chain(self.milestones.iter().map(|m| m.time))
+            .chain(self.now)
+            .filter(|t| t.is_finite());
+        let (first, last) = times.fold((f64::MAX, f64::MIN), |(first, last), t| (first.min(t), last.max(t)));
+        if first > last {
+            return None;
+        }
+
+        let top = (self.rows.len() as f64) - 0.5;
+        Some((GraphCoord::new(first, -0.5), GraphCoord::new(last, top)))
+    }
+
+    fn y_categories(&self) -> Vec<(f64, Category<'_>)> {
+        self.rows.iter().enumerate().map(|(row, &name)| (self.row_position(row), Category::Name(name))).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::mock_canvas::MockCanvas;
+    use graph_dimensions::GraphDimensions;
+    use options::Orientation;
+    use pixel::{Color, GraphCoord};
+    use plottable::{Plottable, Category};
+    use plottable::primitives::Bar;
+
+    #[test]
+    fn test_layout() {
+        let rows = ["build", "test", "deploy"];
+        let tasks = [Task::new("compile", 0.0, 4.0, 0), Task::new("unit", 4.0, 6.0, 1), Task::new("ship", 7.0, 8.0, 2)];
+        let milestones = [Milestone::new("release", 9.0, 2)];
+        let timeline = TimelineSeries::new(&rows, &tasks).milestones(&milestones).now(-1.0);
+
+        // the first row should be at the top
+        assert_eq!(timeline.row_position(0), 2.0);
+        assert_eq!(timeline.bars()[1], Bar::new(1.0, 4.0, 6.0, 0.6, Orientation::Horizontal));
+
+        // the milestone and the now line are on the graph as well as the tasks
+        assert_eq!(timeline.data_extent(), Some((GraphCoord::new(-1.0, -0.5), GraphCoord::new(9.0, 2.5))));
+
+        // each row is named along the y axis
+        assert_eq!(timeline.y_categories(), vec!(
+            (2.0, Category::Name("build")), (1.0, Category::Name("test")), (0.0, Category::Name("deploy")),
+        ));
+    }
+
+    #[test]
+    fn test_plot() {
+        let mut fake_canvas = MockCanvas::new();
+        let rows = ["build", "test"];
+        let tasks = [Task::new("compile", 0.0, 4.0, 0), Task::new("unit", 4.0, 6.0, 1).color(Color(0, 128, 0))];
+        let milestones = [Milestone::new("green", 6.0, 1)];
+        let dependencies = [(0, 1), (0, 5)];
+
+        let timeline = TimelineSeries::new(&rows, &tasks)
+            .milestones(&milestones)
+            .dependencies(&dependencies)
+            .now(3.0);
+        let (min, max) = timeline.data_extent().unwrap();
+
+        let mut dims = GraphDimensions::new(600.0, 600.0);
+        dims.min = min;
+        dims.max = max;
+
+        // a dependency on a task that doesn't exist should be skipped
+        assert_eq!(timeline.plot(&dims, &mut fake_canvas), Ok(()));
+
+        // the now line is drawn last, straight up the graph
+        let &(start, end, color) = fake_canvas.lines.last().unwrap();
+        assert_eq!((color, start.x), (Color(220, 0, 0), end.x));
+    }
+}
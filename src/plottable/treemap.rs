@@ -0,0 +1,343 @@
+use plottable::Plottable;
+use colormap::{Colormap, PALETTE};
+use graph_dimensions::GraphDimensions;
+use canvas::Canvas;
+use pixel::{Color, GraphCoord, Pixel};
+use std::f64;
+
+/// A node in the tree drawn by a `Treemap`, a leaf has a weight and a group's
+/// weight is the total weight of everything in it
+#[derive(Clone, PartialEq, Debug)]
+pub struct TreeNode<'a> {
+    pub name: &'a str,
+    pub weight: f64,
+    pub children: Vec<TreeNode<'a>>,
+    pub color: Option<Color>,
+}
+
+impl <'a> TreeNode<'a> {
+    pub fn leaf(name: &'a str, weight: f64) -> TreeNode<'a> {
+        TreeNode { name: name, weight: weight, children: Vec::new(), color: None }
+    }
+
+    pub fn group(name: &'a str, children: Vec<TreeNode<'a>>) -> TreeNode<'a> {
+        TreeNode { name: name, weight: 0.0, children: children, color: None }
+    }
+
+    /// Draws this node, and anything inside of it without its own colour, in `color`
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = Some(color);
+        self
+    }
+
+    /// The weight of a leaf or the total weight of every leaf in a group,
+    /// anything that isn't a positive number counts as 0
+    pub fn total(&self) -> f64 {
+        if self.children.is_empty() {
+            if self.weight > 0.0 { self.weight } else { 0.0 }
+        } else {
+            self.children.iter().map(|c| c.total()).sum()
+        }
+    }
+}
+
+/// A rectangle laid out for one node of a `Treemap`, in pixels
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Tile<'a> {
+    pub name: &'a str,
+    pub depth: usize,
+    pub bottom_left: Pixel,
+    pub top_right: Pixel,
+    pub color: Color,
+    pub is_leaf: bool,
+}
+
+impl <'a> Tile<'a> {
+    fn width(&self) -> f64 {
+        self.top_right.x - self.bottom_left.x
+    }
+
+    fn height(&self) -> f64 {
+        self.top_right.y - self.bottom_left.y
+    }
+}
+
+/// `Treemap` splits the graph into a rectangle for each node of a tree with an
+/// area in proportion to its weight, using the squarified layout so the
+/// rectangles are as close to square as possible
+/// It lays itself out inside the graph so it should be drawn without an `Axis`
+pub struct Treemap<'a> {
+    root: &'a TreeNode<'a>,
+    colormap: Option<Colormap>,
+    padding: f64,
+    header: f64,
+}
+
+impl <'a> Treemap<'a> {
+    pub fn new(root: &'a TreeNode<'a>) -> Self {
+        Treemap {
+            root: root,
+            colormap: None,
+            padding: 3.0,
+            header: 16.0,
+        }
+    }
+
+    /// Colours each leaf by its weight instead of by the group it is in
+    pub fn colormap<C: Into<Option<Colormap>>>(mut self, colormap: C) -> Self {
+        self.colormap = colormap.into();
+        self
+    }
+
+    /// The gap in pixels between the edge of a group and the nodes inside of it
+    pub fn padding(mut self, padding: f64) -> Self {
+        self.padding = padding.max(0.0);
+        self
+    }
+
+    /// The height in pixels left at the top of each group for its name
+    pub fn header(mut self, header: f64) -> Self {
+        self.header = header.max(0.0);
+        self
+    }
+
+    /// Every tile in the order it should be drawn, a group comes before
+    /// everything inside of it
+    pub fn tiles(&self, bounds: &GraphDimensions) -> Vec<Tile<'a>> {
+        let (bottom_left, top_right) = bounds.plot_area();
+        let mut tiles = Vec::new();
+
+        let weights = leaf_weights(self.root);
+        let range = weights.iter().fold((f64::MAX, f64::MIN), |(min, max), &w| (min.min(w), max.max(w)));
+
+        let root: &'a TreeNode<'a> = self.root;
+        self.layout_children(root, (bottom_left, top_right), 0, None, range, &mut tiles);
+        tiles
+    }
+
+    fn layout_children(&self, node: &'a TreeNode<'a>, (bottom_left, top_right): (Pixel, Pixel), depth: usize,
+                       color: Option<Color>, range: (f64, f64), tiles: &mut Vec<Tile<'a>>) {
+
+        let mut children = node.children.iter().filter(|c| c.total() > 0.0).collect::<Vec<_>>();
+        children.sort_by(|a, b| b.total().partial_cmp(&a.total()).expect("weights should be finite"));
+
+        let weights = children.iter().map(|c| c.total()).collect::<Vec<_>>();
+        let rects = squarify(&weights, bottom_left, top_right);
+
+        for (n, (child, (child_bottom_left, child_top_right))) in children.into_iter().zip(rects).enumerate() {
+            let group_color = child.color.or(color).unwrap_or(PALETTE[n % PALETTE.len()]);
+            let is_leaf = child.children.is_empty();
+
+            let tile_color = match self.colormap {
+                Some(colormap) if is_leaf => colormap.color_for(child.total(), range.0, range.1),
+                _ => group_color,
+            };
+
+            tiles.push(Tile {
+                name: child.name,
+                depth: depth,
+                bottom_left: child_bottom_left,
+                top_right: child_top_right,
+                color: tile_color,
+                is_leaf: is_leaf,
+            });
+
+            if !is_leaf {
+                // the children go inside the padding and below the header
+                let inner_bottom_left = Pixel::new(child_bottom_left.x + self.padding, child_bottom_left.y + self.padding);
+                let inner_top_right = Pixel::new(child_top_right.x - self.padding,
+                                                 child_top_right.y - self.padding - self.header);
+
+                if inner_top_right.x > inner_bottom_left.x && inner_top_right.y > inner_bottom_left.y {
+                    let inner = (inner_bottom_left, inner_top_right);
+                    self.layout_children(child, inner, depth + 1, Some(group_color), range, tiles);
+                }
+            }
+        }
+    }
+}
+
+impl <'a> Plottable for Treemap<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        for tile in self.tiles(bounds) {
+            // groups are drawn lighter so the nodes inside of them stand out
+            let Color(r, g, b) = tile.color;
+            let color = if tile.is_leaf { tile.color } else { Color(lighten(r), lighten(g), lighten(b)) };
+
+            canvas.set_color(color);
+            canvas.fill_rect(tile.bottom_left, tile.width(), tile.height())?;
+            canvas.set_color(Color(0, 0, 0));
+            canvas.draw_rect(tile.bottom_left, tile.width(), tile.height())?;
+
+            // names are only written if they fit inside the tile,
+            // a group's name goes in its header
+            let (text_width, text_height) = canvas.text_size(tile.name)?;
+            if tile.is_leaf {
+                if text_width + 4.0 <= tile.width() && text_height + 4.0 <= tile.height() {
+                    let centre = Pixel::new((tile.bottom_left.x + tile.top_right.x) / 2.0,
+                                            (tile.bottom_left.y + tile.top_right.y) / 2.0);
+                    canvas.write_text_centred(tile.name, centre)?;
+                }
+            } else if text_width + 2.0 * self.padding <= tile.width() && text_height <= self.header + self.padding {
+                let bottom_left = Pixel::new(tile.bottom_left.x + self.padding, tile.top_right.y - self.padding - text_height);
+                canvas.write_text(tile.name, bottom_left)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn data_extent(&self) -> Option<(GraphCoord, GraphCoord)> {
+        Some((GraphCoord::new(0.0, 0.0), GraphCoord::new(1.0, 1.0)))
+    }
+}
+
+fn lighten(c: u8) -> u8 {
+    c + (255 - c) / 2
+}
+
+// the weight of every leaf that will be drawn
+fn leaf_weights(node: &TreeNode) -> Vec<f64> {
+    if node.children.is_empty() {
+        if node.total() > 0.0 { vec!(node.total()) } else { vec!() }
+    } else {
+        node.children.iter().flat_map(|c| leaf_weights(c)).collect()
+    }
+}
+
+/// Lays out a rectangle for each weight between `bottom_left` and `top_right`,
+/// the weights should be sorted largest first
+///
+/// The rectangles are laid out in rows along the shorter side, a row keeps
+/// growing while adding the next weight makes its worst aspect ratio better
+pub fn squarify(weights: &[f64], bottom_left: Pixel, top_right: Pixel) -> Vec<(Pixel, Pixel)> {
+    let total = weights.iter().fold(0.0, |acc, &w| acc + w);
+    let (mut x, y) = (bottom_left.x, bottom_left.y);
+    let (mut width, mut height) = (top_right.x - bottom_left.x, top_right.y - bottom_left.y);
+
+    let mut rects = Vec::with_capacity(weights.len());
+    if total <= 0.0 || width <= 0.0 || height <= 0.0 {
+        return rects;
+    }
+
+    let scale = width * height / total;
+    let areas = weights.iter().map(|&w| w * scale).collect::<Vec<_>>();
+
+    let mut start = 0;
+    while start < areas.len() {
+        let side = width.min(height);
+
+        let mut end = start + 1;
+        while end < areas.len() && worst(&areas[start..(end + 1)], side) <= worst(&areas[start..end], side) {
+            end += 1;
+        }
+
+        let row = &areas[start..end];
+        let row_area = row.iter().fold(0.0, |acc, &a| acc + a);
+        let thickness = row_area / side;
+
+        // the row runs along the shorter side, taking that much off the longer side
+        let mut offset = 0.0;
+        for &area in row.iter() {
+            let length = area / thickness;
+            if width >= height {
+                rects.push((Pixel::new(x, y + offset), Pixel::new(x + thickness, y + offset + length)));
+            } else {
+                rects.push((Pixel::new(x + offset, y + height - thickness), Pixel::new(x + offset + length, y + height)));
+            }
+            offset += length;
+        }
+
+        if width >= height {
+            x += thickness;
+            width -= thickness;
+        } else {
+            height -= thickness;
+        }
+
+        start = end;
+    }
+
+    rects
+}
+
+// The largest aspect ratio of any rectangle in a row laid along `side`
+fn worst(row: &[f64], side: f64) -> f64 {
+    let sum = row.iter().fold(0.0, |acc, &a| acc + a);
+    let (min, max) = row.iter().fold((f64::MAX, f64::MIN), |(min, max), &a| (min.min(a), max.max(a)));
+    let side2 = side * side;
+
+    (side2 * max / (sum * sum)).max(sum * sum / (side2 * min))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::mock_canvas::MockCanvas;
+    use colormap::Colormap;
+    use graph_dimensions::GraphDimensions;
+    use pixel::{Color, GraphCoord, Pixel};
+    use plottable::Plottable;
+
+    fn area(&(a, b): &(Pixel, Pixel)) -> f64 {
+        (b.x - a.x) * (b.y - a.y)
+    }
+
+    #[test]
+    fn test_squarify() {
+        // the example from the paper by Bruls, Huizing and van Wijk
+        let weights = [6.0, 6.0, 4.0, 3.0, 2.0, 2.0, 1.0];
+        let rects = squarify(&weights, Pixel::new(0.0, 0.0), Pixel::new(6.0, 4.0));
+
+        assert_eq!(rects.len(), 7);
+        for (rect, &weight) in rects.iter().zip(weights.iter()) {
+            assert!((area(rect) - weight).abs() < 1e-9);
+        }
+
+        // the first row should be the two largest, side by side down the shorter side
+        assert_eq!(rects[0], (Pixel::new(0.0, 0.0), Pixel::new(3.0, 2.0)));
+        assert_eq!(rects[1], (Pixel::new(0.0, 2.0), Pixel::new(3.0, 4.0)));
+
+        // nothing should be outside of the rectangle
+        assert!(rects.iter().all(|&(a, b)| a.x > -1e-9 && a.y > -1e-9 && b.x < 6.0 + 1e-9 && b.y < 4.0 + 1e-9));
+    }
+
+    #[test]
+    fn test_tiles() {
+        let tree = TreeNode::group("chartrs", vec!(
+            TreeNode::group("plottable", vec!(TreeNode::leaf("graphs", 30.0), TreeNode::leaf("axis", 10.0))),
+            TreeNode::leaf("labeller", 20.0).color(Color(255, 0, 0)),
+            TreeNode::leaf("empty", 0.0),
+        ));
+        assert_eq!(tree.total(), 60.0);
+
+        let mut dims = GraphDimensions::new(600.0, 600.0);
+        dims.min = GraphCoord::new(0.0, 0.0);
+        dims.max = GraphCoord::new(1.0, 1.0);
+
+        let tiles = Treemap::new(&tree).tiles(&dims);
+        let names = tiles.iter().map(|t| (t.name, t.depth)).collect::<Vec<_>>();
+        assert_eq!(names, vec!(("plottable", 0), ("graphs", 1), ("axis", 1), ("labeller", 0)));
+
+        // children share the colour of their group unless they have their own
+        assert_eq!(tiles[1].color, tiles[0].color);
+        assert_eq!(tiles[3].color, Color(255, 0, 0));
+
+        // with a colormap the leaves are coloured by weight instead
+        let tiles = Treemap::new(&tree).colormap(Colormap::Grayscale).tiles(&dims);
+        assert_eq!(tiles[1].color, Color(255, 255, 255));
+        assert_eq!(tiles[2].color, Color(0, 0, 0));
+    }
+
+    #[test]
+    fn test_plot() {
+        let mut fake_canvas = MockCanvas::new();
+        let tree = TreeNode::group("root", vec!(TreeNode::leaf("a", 1.0), TreeNode::leaf("a very long name", 0.01)));
+
+        let mut dims = GraphDimensions::new(600.0, 600.0);
+        dims.min = GraphCoord::new(0.0, 0.0);
+        dims.max = GraphCoord::new(1.0, 1.0);
+
+        assert_eq!(Treemap::new(&tree).plot(&dims, &mut fake_canvas), Ok(()));
+    }
+}
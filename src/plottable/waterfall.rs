@@ -0,0 +1,237 @@
+use options::Orientation;
+use plottable::{Plottable, LegendEntry, Category};
+use plottable::primitives::{Bar, Line};
+use graph_dimensions::GraphDimensions;
+use canvas::Canvas;
+use pixel::{Color, GraphCoord};
+use std::f64;
+
+/// A single step of a `WaterfallSeries`
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum WaterfallStep<'a> {
+    /// A labelled amount added to, or taken away from, the running total
+    Delta(&'a str, f64),
+    /// A bar from zero up to the running total so far
+    Subtotal(&'a str),
+}
+
+/// `WaterfallSeries` draws each delta as a bar floating between the running
+/// total before and after it, the steps are placed one after another along the
+/// x axis, which a `Graph2D` labels with the name of each step
+pub struct WaterfallSeries<'a> {
+    steps: &'a [WaterfallStep<'a>],
+    legend: bool,
+    start: f64,
+    total: Option<&'a str>,
+    up_color: Color,
+    down_color: Color,
+    total_color: Color,
+    connectors: bool,
+    width: f64,
+}
+
+impl <'a> WaterfallSeries<'a> {
+    pub fn new(steps: &'a [WaterfallStep<'a>]) -> Self {
+        WaterfallSeries {
+            steps: steps,
+            legend: false,
+            start: 0.0,
+            total: None,
+            up_color: Color(0, 160, 0),
+            down_color: Color(200, 0, 0),
+            total_color: Color(60, 90, 180),
+            connectors: true,
+            width: 0.8,
+        }
+    }
+
+    /// Shows the colours of the increases and decreases in the legend
+    pub fn legend(mut self, legend: bool) -> Self {
+        self.legend = legend;
+        self
+    }
+
+    /// The running total before the first step, such as an opening balance
+    pub fn start(mut self, start: f64) -> Self {
+        self.start = start;
+        self
+    }
+
+    /// Adds a bar with the final total after the last step
+    pub fn total<T: Into<Option<&'a str>>>(mut self, label: T) -> Self {
+        self.total = label.into();
+        self
+    }
+
+    pub fn up_color(mut self, color: Color) -> Self {
+        self.up_color = color;
+        self
+    }
+
+    pub fn down_color(mut self, color: Color) -> Self {
+        self.down_color = color;
+        self
+    }
+
+    /// The colour of every subtotal and total bar
+    pub fn total_color(mut self, color: Color) -> Self {
+        self.total_color = color;
+        self
+    }
+
+    /// Draws a line from the end of each bar across to the start of the next
+    pub fn connectors(mut self, connectors: bool) -> Self {
+        self.connectors = connectors;
+        self
+    }
+
+    /// The width of each bar, a width of 1 leaves no gap between bars
+    pub fn width(mut self, width: f64) -> Self {
+        self.width = width;
+        self
+    }
+
+    /// Every bar in order along with its label and colour
+    pub fn bars(&self) -> Vec<(&'a str, Color, Bar)> {
+        let mut running = self.start;
+        let mut bars = Vec::new();
+
+        let steps = self.steps.iter().cloned().chain(self.total.map(WaterfallStep::Subtotal));
+        for (n, step) in steps.enumerate() {
+            let position = n as f64;
+
+            let bar = match step {
+                WaterfallStep::Delta(label, delta) => {
+                    let color = if delta >= 0.0 { self.up_color } else { self.down_color };
+                    let bar = Bar::new(position, running, running + delta, self.width, Orientation::Vertical);
+                    running += delta;
+                    (label, color, bar)
+                },
+                WaterfallStep::Subtotal(label) => {
+                    (label, self.total_color, Bar::new(position, 0.0, running, self.width, Orientation::Vertical))
+                },
+            };
+            bars.push(bar);
+        }
+
+        bars
+    }
+}
+
+impl <'a> Plottable for WaterfallSeries<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        let bars = self.bars();
+
+        for &(_, color, ref bar) in bars.iter() {
+            canvas.set_color(color);
+            bar.plot(bounds, canvas)?;
+        }
+
+        if self.connectors {
+            canvas.set_color(Color(128, 128, 128));
+            for pair in bars.windows(2) {
+                let (ref prev, ref next) = (pair[0].2, pair[1].2);
+                // every bar, including subtotals, ends at the running total after it
+                let start = GraphCoord::new(prev.position + prev.width / 2.0, prev.end);
+                let end = GraphCoord::new(next.position - next.width / 2.0, prev.end);
+                Line(start, end).plot(bounds, canvas)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn data_extent(&self) -> Option<(GraphCoord, GraphCoord)> {
+        let bars = self.bars();
+        if bars.is_empty() {
+            return None;
+        }
+
+        let (low, high) = bars.iter().fold((f64::MAX, f64::MIN), |(low, high), (_, _, bar)| {
+            (low.min(bar.start).min(bar.end), high.max(bar.start).max(bar.end))
+        });
+
+        let half_width = self.width / 2.0;
+        let last = (bars.len() - 1) as f64;
+        Some((GraphCoord::new(-half_width, low), GraphCoord::new(last + half_width, high)))
+    }
+
+    fn legend_entries(&self) -> Vec<LegendEntry<'_>> {
+        if !self.legend {
+            return vec!();
+        }
+
+        vec!(LegendEntry::new("Increase", self.up_color), LegendEntry::new("Decrease", self.down_color))
+    }
+
+    fn x_categories(&self) -> Vec<(f64, Category<'_>)> {
+        self.bars().into_iter().map(|(label, _, bar)| (bar.position, Category::Name(label))).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::mock_canvas::MockCanvas;
+    use graph_dimensions::GraphDimensions;
+    use options::Orientation;
+    use pixel::GraphCoord;
+    use plottable::Plottable;
+    use plottable::primitives::Bar;
+
+    fn steps() -> Vec<WaterfallStep<'static>> {
+        vec!(
+            WaterfallStep::Delta("sales", 10.0),
+            WaterfallStep::Delta("refunds", -3.0),
+            WaterfallStep::Subtotal("net"),
+            WaterfallStep::Delta("costs", -12.0),
+        )
+    }
+
+    #[test]
+    fn test_bars() {
+        let steps = steps();
+        let waterfall = WaterfallSeries::new(&steps).start(5.0).total("closing");
+        let bars = waterfall.bars();
+
+        let bar = |position: f64, start: f64, end: f64| Bar::new(position, start, end, 0.8, Orientation::Vertical);
+        assert_eq!(bars, vec!(
+            ("sales", Color(0, 160, 0), bar(0.0, 5.0, 15.0)),
+            ("refunds", Color(200, 0, 0), bar(1.0, 15.0, 12.0)),
+            ("net", Color(60, 90, 180), bar(2.0, 0.0, 12.0)),
+            ("costs", Color(200, 0, 0), bar(3.0, 12.0, 0.0)),
+            ("closing", Color(60, 90, 180), bar(4.0, 0.0, 0.0)),
+        ));
+
+        assert_eq!(waterfall.data_extent(), Some((GraphCoord::new(-0.4, 0.0), GraphCoord::new(4.4, 15.0))));
+    }
+
+    #[test]
+    fn test_plot() {
+        let mut fake_canvas = MockCanvas::new();
+        let steps = steps();
+        let waterfall = WaterfallSeries::new(&steps).total("closing").connectors(true);
+
+        let (min, max) = waterfall.data_extent().unwrap();
+        let mut dims = GraphDimensions::new(600.0, 600.0);
+        dims.min = min;
+        dims.max = max;
+
+        assert_eq!(waterfall.plot(&dims, &mut fake_canvas), Ok(()));
+        // a connector joins each pair of bars
+        let connectors = fake_canvas.lines.iter().filter(|l| l.2 == Color(128, 128, 128)).count();
+        assert_eq!(connectors, 4);
+
+        // each step is labelled along the x axis
+        assert_eq!(waterfall.x_categories(), vec!(
+            (0.0, Category::Name("sales")), (1.0, Category::Name("refunds")), (2.0, Category::Name("net")),
+            (3.0, Category::Name("costs")), (4.0, Category::Name("closing")),
+        ));
+
+        // the legend is only shown when asked for
+        assert!(waterfall.legend_entries().is_empty());
+        let waterfall = waterfall.legend(true);
+        assert_eq!(waterfall.legend_entries(), vec!(LegendEntry::new("Increase", Color(0, 160, 0)),
+                                                    LegendEntry::new("Decrease", Color(200, 0, 0))));
+    }
+}
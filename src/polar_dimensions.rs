@@ -0,0 +1,130 @@
+use pixel::Pixel;
+use data_set::DataSet;
+use options::{ZeroDirection, Winding};
+use std::f64;
+use std::f64::consts::PI;
+
+/// `PolarDimensions` converts a (theta, r) co-ordinate into a `Pixel`
+/// the graph is a circle centred on the canvas with `r_max` at its edge
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PolarDimensions {
+    pub r_max: f64,
+
+    pub height: f64,
+    pub width: f64,
+
+    pub border: f64,
+
+    pub zero_direction: ZeroDirection,
+    pub winding: Winding,
+}
+
+impl PolarDimensions {
+    pub fn new(width: f64, height: f64) -> PolarDimensions {
+        PolarDimensions {
+            r_max: 0.0,
+
+            height: height,
+            width: width,
+
+            border: 0.1,
+
+            zero_direction: ZeroDirection::East,
+            winding: Winding::Anticlockwise,
+        }
+    }
+
+    /// Grows `r_max` so every point in `ds` is on the graph, the y value
+    /// of each point is used as r
+    pub fn adjust_for(&mut self, ds: &DataSet) {
+        let r = ds.data_points.iter()
+            .map(|p| p.y)
+            .filter(|r| r.is_finite())
+            .fold(self.r_max, |acc, r| acc.max(r));
+
+        self.r_max = r;
+    }
+
+    pub fn centre(&self) -> Pixel {
+        Pixel::new(self.width / 2.0, self.height / 2.0)
+    }
+
+    /// The radius of the graph in pixels
+    pub fn radius(&self) -> f64 {
+        let size = self.width.min(self.height);
+        (size / 2.0) - (size * self.border)
+    }
+
+    /// The angle on the screen, anticlockwise from the right, for `theta`
+    pub fn screen_angle(&self, theta: f64) -> f64 {
+        let zero = match self.zero_direction {
+            ZeroDirection::East => 0.0,
+            ZeroDirection::North => PI / 2.0,
+            ZeroDirection::West => PI,
+            ZeroDirection::South => 3.0 * PI / 2.0,
+        };
+
+        match self.winding {
+            Winding::Anticlockwise => zero + theta,
+            Winding::Clockwise => zero - theta,
+        }
+    }
+
+    /// Converts a point at `theta` radians and `r` from the centre to a `Pixel`
+    /// returns `None` if `r` is off the graph
+    pub fn convert_to_pixel(&self, theta: f64, r: f64) -> Option<Pixel> {
+        if self.off_grid(r) || !theta.is_finite() {
+            return None;
+        }
+
+        Some(self.pixel_at_radius(theta, self.radius() * (r / self.r_max)))
+    }
+
+    /// The `Pixel` at `theta` that is `distance` pixels away from the centre
+    /// this can be used to place anything outside of the graph such as labels
+    pub fn pixel_at_radius(&self, theta: f64, distance: f64) -> Pixel {
+        let angle = self.screen_angle(theta);
+        let centre = self.centre();
+
+        Pixel::new(centre.x + distance * angle.cos(), centre.y + distance * angle.sin())
+    }
+
+    pub fn off_grid(&self, r: f64) -> bool {
+        !(r >= 0.0 && r <= self.r_max)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use options::{ZeroDirection, Winding};
+    use pixel::Pixel;
+    use std::f64::consts::PI;
+
+    fn assert_close(a: Option<Pixel>, b: Pixel) {
+        let a = a.expect("pixel should be on the graph");
+        assert!((a.x - b.x).abs() < 1e-9 && (a.y - b.y).abs() < 1e-9, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_convert_to_pixel() {
+        let mut dims = PolarDimensions::new(600.0, 400.0);
+        dims.r_max = 10.0;
+
+        // the radius is taken from the smaller side, 400 / 2 - 40
+        assert_eq!(dims.radius(), 160.0);
+        assert_close(dims.convert_to_pixel(0.0, 0.0), Pixel::new(300.0, 200.0));
+        assert_close(dims.convert_to_pixel(0.0, 10.0), Pixel::new(460.0, 200.0));
+        assert_close(dims.convert_to_pixel(PI / 2.0, 5.0), Pixel::new(300.0, 280.0));
+
+        // turning zero to north and going clockwise should put 90 degrees to the east
+        dims.zero_direction = ZeroDirection::North;
+        dims.winding = Winding::Clockwise;
+        assert_close(dims.convert_to_pixel(PI / 2.0, 10.0), Pixel::new(460.0, 200.0));
+        assert_close(dims.convert_to_pixel(0.0, 10.0), Pixel::new(300.0, 360.0));
+
+        // anything further out than r_max is off the graph
+        assert_eq!(dims.convert_to_pixel(0.0, 11.0), None);
+        assert_eq!(dims.convert_to_pixel(0.0, -1.0), None);
+    }
+}
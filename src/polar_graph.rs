@@ -0,0 +1,282 @@
+use data_set::DataSet;
+use options::{PlotStyle, PolarOptions, AngleUnit, PointStyle};
+use plottable::{Plottable, Legend};
+use plottable::primitives::Point;
+use labeller::{Labeller, Label};
+use pixel::{Color, GraphCoord};
+use polar_dimensions::PolarDimensions;
+use graph_dimensions::GraphDimensions;
+use canvas::Canvas;
+use std::f64::consts::PI;
+
+// The largest change in angle drawn as a single straight line
+// anything larger is split up so lines follow the arc between two points
+const MAX_ARC_STEP: f64 = PI / 90.0;
+
+// The most straight lines a single arc is split into, this is a few full turns
+// so an angle given in degrees by mistake can't make drawing hang
+const MAX_ARC_STEPS: f64 = 2000.0;
+
+/// A `PolarGraph` plots each `DataSet` with the x value of each point as
+/// the angle theta in radians and the y value as the distance r from the centre
+pub struct PolarGraph<'a, 'c, 'o, T: Canvas + 'c> {
+    pub data_sets: Vec<&'a DataSet<'a>>,
+    canvas: &'c mut T,
+    pub dimensions: PolarDimensions,
+    opts: &'o PolarOptions<'o>,
+}
+
+impl <'a, 'c, 'o, T: Canvas> PolarGraph<'a, 'c, 'o, T> {
+    pub fn new(canvas: &'c mut T, data_sets: Vec<&'a DataSet<'a>>, opts: &'o PolarOptions<'o>) -> Self {
+        let dimensions = PolarGraph::<T>::dimensions_for(canvas, &data_sets, opts);
+
+        PolarGraph {
+            data_sets: data_sets,
+            canvas: canvas,
+            dimensions: dimensions,
+            opts: opts,
+        }
+    }
+
+    fn dimensions_for(canvas: &T, data_sets: &[&DataSet], opts: &PolarOptions) -> PolarDimensions {
+        let (width, height) = canvas.get_size();
+        let mut dimensions = PolarDimensions::new(width, height);
+        dimensions.border = opts.border;
+        dimensions.zero_direction = opts.zero_direction;
+        dimensions.winding = opts.winding;
+
+        for ds in data_sets.iter() {
+            dimensions.adjust_for(ds);
+        }
+
+        dimensions
+    }
+
+    pub fn add_data_set(&mut self, data_set: &'a DataSet<'a>) {
+        self.dimensions.adjust_for(data_set);
+        self.data_sets.push(data_set);
+    }
+
+    pub fn fit_view_to_data(&mut self) {
+        self.dimensions = PolarGraph::<T>::dimensions_for(self.canvas, &self.data_sets, self.opts);
+    }
+
+    fn rings(&self, loose: bool) -> Label {
+        let mut labeller = Labeller::in_base10();
+        labeller.loose = loose;
+        labeller.search(0.0, self.dimensions.r_max, self.opts.ring_count as i32)
+    }
+
+    fn draw_grid(&mut self, rings: Label) -> Result<(), T::Err> {
+        let dims = self.dimensions;
+        let radius = dims.radius();
+        let centre = dims.centre();
+
+        self.canvas.set_color(Color(0, 0, 0));
+
+        // rings, with their value written between the first two spokes
+        let label_angle = PI / (self.opts.spoke_count.max(1) as f64);
+        let mut r = rings.step;
+        while rings.step > 0.0 && r <= dims.r_max * (1.0 + 1e-9) {
+            let distance = radius * (r / dims.r_max);
+            let segments = 90;
+            for n in 0..segments {
+                let a = dims.pixel_at_radius(2.0 * PI * (n as f64) / (segments as f64), distance);
+                let b = dims.pixel_at_radius(2.0 * PI * ((n + 1) as f64) / (segments as f64), distance);
+                self.canvas.draw_line(a, b)?;
+            }
+
+            self.canvas.write_num_centred(r, dims.pixel_at_radius(label_angle, distance))?;
+            r += rings.step;
+        }
+
+        // spokes, with the angle written just outside the graph
+        for n in 0..self.opts.spoke_count {
+            let theta = 2.0 * PI * (n as f64) / (self.opts.spoke_count as f64);
+            self.canvas.draw_line(centre, dims.pixel_at_radius(theta, radius))?;
+
+            let label = self.angle_label(n);
+            self.canvas.write_text_centred(&label, dims.pixel_at_radius(theta, radius * 1.08))?;
+        }
+
+        Ok(())
+    }
+
+    fn angle_label(&self, n: i32) -> String {
+        let count = self.opts.spoke_count;
+
+        match self.opts.angle_unit {
+            AngleUnit::Degrees => format!("{}°", ((3600.0 * (n as f64) / (count as f64)).round()) / 10.0),
+            AngleUnit::Radians => radian_label(2 * n, count),
+        }
+    }
+
+    pub fn plot_data_set(&mut self, ds: &'a DataSet) -> Result<(), T::Err> {
+        let dims = self.dimensions;
+        let style = ds.options.point_style;
+
+        if ds.options.plot_style != PlotStyle::Scatter {
+            self.canvas.set_color(ds.choose_color());
+
+            for pair in ds.data_points.windows(2) {
+                self.plot_arc(pair[0], pair[1])?;
+            }
+        }
+
+        // scatter graphs should always have a marker for each point
+        let style = match (ds.options.plot_style, style) {
+            (PlotStyle::Scatter, PointStyle::Nothing) => PointStyle::Cross,
+            _ => style,
+        };
+
+        for point in ds.data_points.iter() {
+            if let Some(pix) = dims.convert_to_pixel(point.x, point.y) {
+                self.canvas.set_color(ds.choose_color());
+                Point::draw_marker(style, pix, self.canvas)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    // Draws the line between two points by changing theta and r at
+    // the same rate, so a constant r follows the ring around the graph
+    fn plot_arc(&mut self, a: GraphCoord, b: GraphCoord) -> Result<(), T::Err> {
+        let steps = ((b.x - a.x).abs() / MAX_ARC_STEP).ceil().clamp(1.0, MAX_ARC_STEPS);
+        if !steps.is_finite() {
+            return Ok(());
+        }
+
+        let steps = steps as i32;
+        let mut prev = self.dimensions.convert_to_pixel(a.x, a.y);
+        for n in 1..(steps + 1) {
+            let t = (n as f64) / (steps as f64);
+            let pix = self.dimensions.convert_to_pixel(a.x + t * (b.x - a.x), a.y + t * (b.y - a.y));
+
+            if let (Some(start), Some(end)) = (prev, pix) {
+                self.canvas.draw_line(start, end)?;
+            }
+            prev = pix;
+        }
+
+        Ok(())
+    }
+
+    fn redraw_data_sets(&mut self, prettify_rings: bool) -> Result<(), T::Err> {
+        self.canvas.set_color(Color(255, 255, 255));
+        self.canvas.clear();
+
+        if self.dimensions.r_max <= 0.0 {
+            self.canvas.show();
+            return Ok(());
+        }
+
+        let rings = self.rings(prettify_rings);
+        if prettify_rings {
+            self.dimensions.r_max = rings.max;
+        }
+        self.draw_grid(rings)?;
+
+        let data_sets = self.data_sets.clone();
+        for ds in data_sets.iter() {
+            self.plot_data_set(ds)?;
+        }
+
+        // the legend goes in the top right corner of the canvas
+        let mut legend_bounds = GraphDimensions::new(self.dimensions.width, self.dimensions.height);
+        legend_bounds.min = GraphCoord::new(0.0, 0.0);
+        legend_bounds.max = GraphCoord::new(1.0, 1.0);
+        Legend(&data_sets, &[]).plot(&legend_bounds, self.canvas)?;
+
+        let size = self.dimensions.width.min(self.dimensions.height);
+        let top = self.dimensions.height - (size * self.dimensions.border / 2.0);
+        self.canvas.set_color(Color(0, 0, 0));
+        self.canvas.write_text_centred(self.opts.label, (self.dimensions.width / 2.0, top))?;
+
+        self.canvas.show();
+        Ok(())
+    }
+
+    pub fn show(&mut self) -> Result<(), T::Err> {
+        self.redraw_data_sets(true)
+    }
+
+    /// Zooms in or out by multiplying the largest r on the graph by `factor`
+    pub fn scale(&mut self, factor: f64) -> Result<(), T::Err> {
+        self.dimensions.r_max *= factor;
+        self.redraw_data_sets(false)
+    }
+}
+
+// Writes numerator * pi / denominator as a simplified fraction
+fn radian_label(numerator: i32, denominator: i32) -> String {
+    let divisor = gcd(numerator, denominator).max(1);
+    let (n, d) = (numerator / divisor, denominator / divisor);
+
+    match (n, d) {
+        (0, _) => "0".to_string(),
+        (1, 1) => "π".to_string(),
+        (n, 1) => format!("{}π", n),
+        (1, d) => format!("π/{}", d),
+        (n, d) => format!("{}π/{}", n, d),
+    }
+}
+
+fn gcd(a: i32, b: i32) -> i32 {
+    if b == 0 { a.abs() } else { gcd(b, a % b) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::mock_canvas::MockCanvas;
+    use data_set::DataSet;
+    use options::{DataSetOptions, PolarOptions};
+
+    #[test]
+    fn test_radian_label() {
+        assert_eq!(radian_label(0, 8), "0");
+        assert_eq!(radian_label(2, 8), "π/4");
+        assert_eq!(radian_label(6, 8), "3π/4");
+        assert_eq!(radian_label(8, 8), "π");
+        assert_eq!(radian_label(12, 8), "3π/2");
+    }
+
+    #[test]
+    fn test_show() {
+        let mut fake_canvas = MockCanvas::new();
+        let opts = DataSetOptions::default().name("cardioid");
+        let polar_opts = PolarOptions::default();
+
+        let theta = (0..100).map(|n| (n as f64) * 0.0628).collect::<Vec<_>>();
+        let ds = DataSet::from_fn(theta, &opts, |t| 1.0 + t.cos());
+
+        {
+            let mut graph = PolarGraph::new(&mut fake_canvas, vec!(&ds), &polar_opts);
+            assert_eq!(graph.show(), Ok(()));
+
+            // the rings should have been made to look nice, so r_max can only grow
+            assert!(graph.dimensions.r_max >= 2.0);
+        }
+
+        assert_eq!(fake_canvas.shown, 1);
+        assert_eq!(fake_canvas.cleared, 1);
+    }
+
+    #[test]
+    fn test_large_angles() {
+        let mut fake_canvas = MockCanvas::new();
+        let opts = DataSetOptions::default();
+        let polar_opts = PolarOptions::default();
+
+        // angles in degrees rather than radians are a lot of turns
+        let ds = DataSet::from_vecs(vec!(0.0, 1e9), vec!(1.0, 1.0), &opts).unwrap();
+        {
+            let mut graph = PolarGraph::new(&mut fake_canvas, vec!(&ds), &polar_opts);
+            assert_eq!(graph.plot_arc(GraphCoord::new(0.0, 1.0), GraphCoord::new(1e9, 1.0)), Ok(()));
+        }
+
+        assert!(!fake_canvas.lines.is_empty());
+        assert!(fake_canvas.lines.len() <= MAX_ARC_STEPS as usize);
+    }
+}
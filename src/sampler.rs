@@ -0,0 +1,217 @@
+use pixel::GraphCoord;
+use graph_dimensions::GraphDimensions;
+use std::f64;
+
+// The amount of evenly spaced samples taken before any are refined
+const INITIAL_SAMPLES: usize = 64;
+
+// How many times each of the initial gaps can be halved
+const MAX_DEPTH: u32 = 12;
+
+// The furthest in pixels the curve can be from a straight line
+// between two samples before the gap between them is refined
+const TOLERANCE: f64 = 0.5;
+
+// The size of the view used when there isn't a graph to sample for
+const DEFAULT_SIZE: (f64, f64) = (800.0, 600.0);
+
+/// A point with both co-ordinates set to NaN, this is put between two samples
+/// where the function is discontinuous so no line is drawn between them
+pub fn gap() -> GraphCoord {
+    GraphCoord::new(f64::NAN, f64::NAN)
+}
+
+/// Samples `f` between `x_min` and `x_max` so that a line joining the
+/// samples is within half a pixel of the curve when drawn on `bounds`
+///
+/// Gaps are refined wherever the curve bends, anywhere the function jumps or
+/// isn't defined is marked with a `gap()` instead of being joined up
+pub fn sample<F>(f: &F, x_min: f64, x_max: f64, bounds: &GraphDimensions) -> Vec<GraphCoord>
+    where F: Fn(f64) -> f64 {
+
+    let mut points = Vec::new();
+    if !(x_min.is_finite() && x_max.is_finite()) || x_max <= x_min {
+        return points;
+    }
+
+    let sampler = Sampler { f: f, bounds: bounds, pixel_size: bounds.pixel_size() };
+    let step = (x_max - x_min) / (INITIAL_SAMPLES as f64);
+
+    let mut prev = sampler.at(x_min);
+    points.push(sampler.point(prev));
+
+    for n in 1..(INITIAL_SAMPLES + 1) {
+        let next = sampler.at(x_min + step * (n as f64));
+        sampler.refine(prev, next, 0, &mut points);
+        prev = next;
+    }
+
+    points
+}
+
+/// A view of `DEFAULT_SIZE` pixels covering every x between `x_min` and `x_max`
+/// and most of the values of `f`, values that are far larger or smaller than
+/// the rest, such as near an asymptote, are left off the view
+pub fn default_bounds<F>(f: &F, x_min: f64, x_max: f64) -> GraphDimensions
+    where F: Fn(f64) -> f64 {
+
+    let mut bounds = GraphDimensions::new(DEFAULT_SIZE.0, DEFAULT_SIZE.1);
+
+    let count = 4 * INITIAL_SAMPLES;
+    let step = (x_max - x_min) / (count as f64);
+    let mut values = (0..(count + 1))
+        .map(|n| f(x_min + step * (n as f64)))
+        .filter(|y| y.is_finite())
+        .collect::<Vec<_>>();
+
+    if values.is_empty() {
+        return bounds;
+    }
+    values.sort_by(|a, b| a.partial_cmp(b).expect("values should be finite"));
+
+    // keep anything within a few interquartile ranges of the middle half of the values
+    let quartile = |q: f64| values[((values.len() - 1) as f64 * q).round() as usize];
+    let (lower, upper) = (quartile(0.25), quartile(0.75));
+    let spread = 3.0 * (upper - lower);
+    let kept = values.iter().cloned().filter(|&y| y >= lower - spread && y <= upper + spread);
+    let (y_min, y_max) = kept.fold((f64::MAX, f64::MIN), |(min, max), y| (min.min(y), max.max(y)));
+
+    // a flat line still needs some height to be drawn on
+    let (y_min, y_max) = if y_max > y_min { (y_min, y_max) } else { (y_min - 1.0, y_max + 1.0) };
+
+    bounds.min = GraphCoord::new(x_min, y_min);
+    bounds.max = GraphCoord::new(x_max, y_max);
+    bounds
+}
+
+struct Sampler<'a, F: 'a> {
+    f: &'a F,
+    bounds: &'a GraphDimensions,
+    pixel_size: (f64, f64),
+}
+
+impl <'a, F> Sampler<'a, F> where F: Fn(f64) -> f64 {
+    fn at(&self, x: f64) -> (f64, f64) {
+        (x, (self.f)(x))
+    }
+
+    fn point(&self, (x, y): (f64, f64)) -> GraphCoord {
+        if y.is_finite() { GraphCoord::new(x, y) } else { gap() }
+    }
+
+    // how far apart two values are in pixels
+    fn pixels(&self, a: f64, b: f64) -> f64 {
+        (a - b).abs() / self.pixel_size.1
+    }
+
+    // true if every value is above the view or every value is below it
+    fn off_view(&self, values: &[f64]) -> bool {
+        values.iter().all(|&y| y > self.bounds.max.y) || values.iter().all(|&y| y < self.bounds.min.y)
+    }
+
+    // Pushes every sample after `a` up to and including `b`
+    fn refine(&self, a: (f64, f64), b: (f64, f64), depth: u32, points: &mut Vec<GraphCoord>) {
+        let m = self.at((a.0 + b.0) / 2.0);
+        let finite = [a.1, m.1, b.1].iter().filter(|y| y.is_finite()).count();
+
+        let refine = if depth >= MAX_DEPTH {
+            false
+        } else if finite == 0 {
+            // there's nothing to draw between two undefined values
+            false
+        } else if finite < 3 {
+            // narrow down where the function stops being defined
+            true
+        } else if self.off_view(&[a.1, m.1, b.1]) {
+            false
+        } else {
+            let straight = (a.1 + b.1) / 2.0;
+            self.pixels(m.1, straight) > TOLERANCE
+        };
+
+        if refine {
+            self.refine(a, m, depth + 1, points);
+            self.refine(m, b, depth + 1, points);
+            return;
+        }
+
+        if depth >= MAX_DEPTH && finite == 3 && self.is_jump(a.1, m.1, b.1) {
+            points.push(gap());
+        }
+        points.push(self.point(b));
+    }
+
+    // After the gap can't be halved any further, a continuous function changes
+    // by about the same amount in both halves, but if one half holds nearly
+    // all of a change of more than a pixel then the function has jumped
+    fn is_jump(&self, a: f64, m: f64, b: f64) -> bool {
+        let total = self.pixels(a, b);
+        let largest_half = self.pixels(a, m).max(self.pixels(m, b));
+
+        largest_half > TOLERANCE && largest_half > 0.9 * total.max(TOLERANCE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graph_dimensions::GraphDimensions;
+    use pixel::GraphCoord;
+
+    fn bounds(min: (f64, f64), max: (f64, f64)) -> GraphDimensions {
+        let mut bounds = GraphDimensions::new(600.0, 600.0);
+        bounds.min = GraphCoord::new(min.0, min.1);
+        bounds.max = GraphCoord::new(max.0, max.1);
+        bounds
+    }
+
+    fn gaps(points: &[GraphCoord]) -> usize {
+        points.iter().filter(|p| p.x.is_nan()).count()
+    }
+
+    #[test]
+    fn test_refines_where_curve_bends() {
+        let bounds = bounds((-1.0, 0.0), (1.0, 1.0));
+        let points = sample(&|x: f64| x.abs(), -1.0, 1.0, &bounds);
+
+        // straight lines shouldn't need any more than the initial samples
+        assert_eq!(sample(&|x: f64| 2.0 * x, -1.0, 1.0, &bounds).len(), INITIAL_SAMPLES + 1);
+        assert_eq!(gaps(&points), 0);
+
+        // a narrow spike should be found and sampled densely around its peak
+        let spike = |x: f64| (-(x * 200.0).powi(2)).exp();
+        let points = sample(&spike, -1.0, 1.0, &bounds);
+        assert!(points.iter().any(|p| p.y > 0.99));
+        assert!(points.len() > INITIAL_SAMPLES + 20);
+        assert!(points.windows(2).all(|pair| pair[0].x < pair[1].x));
+    }
+
+    #[test]
+    fn test_discontinuities() {
+        let bounds = bounds((-3.0, -10.0), (3.0, 10.0));
+
+        // tan has an asymptote at +-pi/2 which shouldn't be joined up
+        let points = sample(&|x: f64| x.tan(), -3.0, 3.0, &bounds);
+        assert_eq!(gaps(&points), 2);
+
+        // a step should be split, a steep but continuous curve should not
+        let step = |x: f64| if x < 0.1 { -5.0 } else { 5.0 };
+        assert_eq!(gaps(&sample(&step, -3.0, 3.0, &bounds)), 1);
+        assert_eq!(gaps(&sample(&|x: f64| (x * 50.0).tanh() * 5.0, -3.0, 3.0, &bounds)), 0);
+
+        // anywhere the function isn't defined should also be left out
+        let points = sample(&|x: f64| x.sqrt(), -3.0, 3.0, &bounds);
+        assert!(points.iter().filter(|p| !p.x.is_nan()).all(|p| p.x >= 0.0));
+        assert!(points.iter().any(|p| p.x >= 0.0 && p.x < 1e-3));
+    }
+
+    #[test]
+    fn test_default_bounds() {
+        let bounds = default_bounds(&|x: f64| x.tan(), -3.0, 3.0);
+
+        // the huge values near each asymptote shouldn't decide the view
+        assert_eq!((bounds.min.x, bounds.max.x), (-3.0, 3.0));
+        assert!(bounds.max.y < 100.0 && bounds.min.y > -100.0);
+        assert!(bounds.max.y > 5.0 && bounds.min.y < -5.0);
+    }
+}
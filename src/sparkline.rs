@@ -0,0 +1,210 @@
+use data_set::DataSet;
+use plottable::Plottable;
+use plottable::graphs::LineSeries;
+use graph_dimensions::GraphDimensions;
+use canvas::Canvas;
+use pixel::{Color, GraphCoord};
+
+// The width and height in pixels of each marker
+const MARKER_SIZE: f64 = 3.0;
+
+/// A `Sparkline` is a small line graph without an axis, legend or borders that
+/// takes up the whole canvas, it is meant to be drawn at sizes as small as 100x20
+/// pixels inside of a table or dashboard
+pub struct Sparkline<'a> {
+    ds: &'a DataSet<'a>,
+    padding: f64,
+    min_marker: Option<Color>,
+    max_marker: Option<Color>,
+    last_marker: Option<Color>,
+    band: Option<(f64, f64)>,
+    band_color: Color,
+}
+
+impl <'a> Sparkline<'a> {
+    pub fn new(ds: &'a DataSet<'a>) -> Self {
+        Sparkline {
+            ds: ds,
+            padding: 2.0,
+            min_marker: None,
+            max_marker: None,
+            last_marker: None,
+            band: None,
+            band_color: Color(225, 225, 225),
+        }
+    }
+
+    /// The gap in pixels between the line and the edge of the canvas
+    pub fn padding(mut self, padding: f64) -> Self {
+        self.padding = padding.max(0.0);
+        self
+    }
+
+    /// Marks the lowest point in `color`
+    pub fn min_marker<C: Into<Option<Color>>>(mut self, color: C) -> Self {
+        self.min_marker = color.into();
+        self
+    }
+
+    /// Marks the highest point in `color`
+    pub fn max_marker<C: Into<Option<Color>>>(mut self, color: C) -> Self {
+        self.max_marker = color.into();
+        self
+    }
+
+    /// Marks the last point in `color`
+    pub fn last_marker<C: Into<Option<Color>>>(mut self, color: C) -> Self {
+        self.last_marker = color.into();
+        self
+    }
+
+    /// Shades the range of values between `low` and `high` that are
+    /// expected, so anything outside of it stands out
+    pub fn band<B: Into<Option<(f64, f64)>>>(mut self, band: B) -> Self {
+        self.band = band.into();
+        self
+    }
+
+    pub fn band_color(mut self, color: Color) -> Self {
+        self.band_color = color;
+        self
+    }
+
+    /// The dimensions of the sparkline on a canvas of `width` by `height` pixels
+    /// the borders are the padding, plus room for the markers, rather than a percentage
+    pub fn dimensions(&self, width: f64, height: f64) -> GraphDimensions {
+        let mut dims = GraphDimensions::new(width, height);
+        dims.adjust_for(self.ds);
+        if let Some((low, high)) = self.band {
+            dims.adjust_for_extent(GraphCoord::new(dims.min.x, low), GraphCoord::new(dims.max.x, high));
+        }
+
+        // a flat line, or a single point, still needs some room to be drawn in
+        if dims.max.x <= dims.min.x {
+            dims.min.x -= 1.0;
+            dims.max.x += 1.0;
+        }
+        if dims.max.y <= dims.min.y {
+            dims.min.y -= 1.0;
+            dims.max.y += 1.0;
+        }
+
+        let border = self.padding + MARKER_SIZE / 2.0;
+        dims.set_border_pixels(border, border);
+        dims
+    }
+
+    /// The lowest, highest and last points, if there are any
+    pub fn key_points(&self) -> Option<(GraphCoord, GraphCoord, GraphCoord)> {
+        let points = self.ds.data_points.iter().cloned()
+            .filter(|p| p.x.is_finite() && p.y.is_finite())
+            .collect::<Vec<_>>();
+        let last = *points.last()?;
+
+        // the first of several equal values is the one marked
+        let min = points.iter().cloned().fold(last, |acc, p| if p.y < acc.y || (p.y == acc.y && p.x < acc.x) { p } else { acc });
+        let max = points.iter().cloned().fold(last, |acc, p| if p.y > acc.y || (p.y == acc.y && p.x < acc.x) { p } else { acc });
+        Some((min, max, last))
+    }
+
+    /// Clears the whole canvas and draws the sparkline on it
+    pub fn show<C: Canvas>(&self, canvas: &mut C) -> Result<(), C::Err> {
+        let (width, height) = canvas.get_size();
+        let dims = self.dimensions(width, height);
+
+        canvas.set_color(Color(255, 255, 255));
+        canvas.clear();
+
+        self.plot(&dims, canvas)?;
+        canvas.show();
+        Ok(())
+    }
+}
+
+impl <'a> Plottable for Sparkline<'a> {
+    fn plot<C: Canvas>(&self, bounds: &GraphDimensions, canvas: &mut C) -> Result<(), C::Err> {
+        if let Some((low, high)) = self.band {
+            let bottom_left = bounds.convert_to_pixel((bounds.min.x, low.max(bounds.min.y)));
+            let top_right = bounds.convert_to_pixel((bounds.max.x, high.min(bounds.max.y)));
+
+            if let (Some(bottom_left), Some(top_right)) = (bottom_left, top_right) {
+                canvas.set_color(self.band_color);
+                canvas.fill_rect(bottom_left, top_right.x - bottom_left.x, top_right.y - bottom_left.y)?;
+            }
+        }
+
+        LineSeries(self.ds).plot(bounds, canvas)?;
+
+        // small squares stay crisp at sizes where a circle or cross wouldn't
+        if let Some((min, max, last)) = self.key_points() {
+            for &(point, color) in &[(min, self.min_marker), (max, self.max_marker), (last, self.last_marker)] {
+                if let (Some(color), Some(pix)) = (color, bounds.convert_to_pixel(point)) {
+                    canvas.set_color(color);
+                    let half = MARKER_SIZE / 2.0;
+                    canvas.fill_rect((pix.x - half, pix.y - half), MARKER_SIZE, MARKER_SIZE)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn data_extent(&self) -> Option<(GraphCoord, GraphCoord)> {
+        if self.ds.data_points.is_empty() {
+            return None;
+        }
+
+        let dims = self.dimensions(0.0, 0.0);
+        Some((dims.min, dims.max))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::mock_canvas::MockCanvas;
+    use data_set::DataSet;
+    use options::DataSetOptions;
+    use pixel::{Color, GraphCoord, Pixel};
+
+    #[test]
+    fn test_dimensions() {
+        let opts = DataSetOptions::default();
+        let ds = DataSet::from_vecs(vec!(0.0, 1.0, 2.0, 3.0), vec!(5.0, 2.0, 8.0, 2.0), &opts).unwrap();
+        let spark = Sparkline::new(&ds).band((4.0, 9.0));
+
+        // the band should be on the graph as well as the data
+        let dims = spark.dimensions(100.0, 20.0);
+        assert_eq!(dims.min, GraphCoord::new(0.0, 2.0));
+        assert_eq!(dims.max, GraphCoord::new(3.0, 9.0));
+
+        // at 100x20 the line should fill everything but the few pixels of padding
+        let close = |a: Option<Pixel>, b: Pixel| a.is_some_and(|a| (a.x - b.x).abs() < 1e-9 && (a.y - b.y).abs() < 1e-9);
+        assert!(close(dims.convert_to_pixel((0.0, 2.0)), Pixel::new(3.5, 3.5)));
+        assert!(close(dims.convert_to_pixel((3.0, 9.0)), Pixel::new(96.5, 16.5)));
+
+        // a flat line should still have a range to be drawn in
+        let flat = DataSet::from_vecs(vec!(0.0, 1.0), vec!(1.0, 1.0), &opts).unwrap();
+        let dims = Sparkline::new(&flat).dimensions(100.0, 20.0);
+        assert!(dims.max.y > dims.min.y);
+    }
+
+    #[test]
+    fn test_show() {
+        let mut fake_canvas = MockCanvas::new();
+        let opts = DataSetOptions::default();
+        let ds = DataSet::from_vecs(vec!(0.0, 1.0, 2.0, 3.0), vec!(5.0, 2.0, 8.0, 2.0), &opts).unwrap();
+
+        let spark = Sparkline::new(&ds)
+            .min_marker(Color(0, 0, 255))
+            .max_marker(Color(255, 0, 0))
+            .last_marker(Color(0, 0, 0));
+
+        // the first of the two lowest points is the minimum
+        assert_eq!(spark.key_points(), Some((GraphCoord::new(1.0, 2.0), GraphCoord::new(2.0, 8.0), GraphCoord::new(3.0, 2.0))));
+
+        assert_eq!(spark.show(&mut fake_canvas), Ok(()));
+        assert_eq!(fake_canvas.color, Color(0, 0, 0));
+        assert_eq!((fake_canvas.shown, fake_canvas.cleared), (1, 1));
+    }
+}
@@ -0,0 +1,113 @@
+//! Quantiles of samples and of a few common distributions, used
+//! for plotting empirical CDFs and Q-Q plots
+
+use std::f64;
+
+/// The finite values of `samples` in ascending order
+pub fn sorted(samples: &[f64]) -> Vec<f64> {
+    let mut sorted = samples.iter().cloned().filter(|s| s.is_finite()).collect::<Vec<_>>();
+    sorted.sort_by(|a, b| a.partial_cmp(b).expect("values should be finite"));
+    sorted
+}
+
+/// The `p` quantile of some sorted values, interpolating linearly
+/// between the two closest values, returns `None` if there aren't any values
+pub fn quantile(sorted: &[f64], p: f64) -> Option<f64> {
+    if sorted.is_empty() || !(0.0..=1.0).contains(&p) {
+        return None;
+    }
+
+    let position = p * ((sorted.len() - 1) as f64);
+    let (below, above) = (position.floor() as usize, position.ceil() as usize);
+    let t = position - (below as f64);
+
+    Some(sorted[below] + t * (sorted[above] - sorted[below]))
+}
+
+/// The probabilities that `n` sorted samples are expected to be at,
+/// each sample is placed in the middle of an equal share of the range
+pub fn plotting_positions(n: usize) -> Vec<f64> {
+    (0..n).map(|i| ((i as f64) + 0.5) / (n as f64)).collect()
+}
+
+/// The quantile of the standard normal distribution for the probability `p`
+///
+/// This uses the rational approximation by Peter Acklam which has a
+/// relative error of less than 1.2e-9
+pub fn normal_quantile(p: f64) -> f64 {
+    const A: [f64; 6] = [-3.969683028665376e+01, 2.209460984245205e+02, -2.759285104469687e+02,
+                         1.38357751867269e+02, -3.066479806614716e+01, 2.506628277459239e+00];
+    const B: [f64; 5] = [-5.447609879822406e+01, 1.615858368580409e+02, -1.556989798598866e+02,
+                         6.680131188771972e+01, -1.328068155288572e+01];
+    const C: [f64; 6] = [-7.784894002430293e-03, -3.223964580411365e-01, -2.400758277161838e+00,
+                         -2.549732539343734e+00, 4.374664141464968e+00, 2.938163982698783e+00];
+    const D: [f64; 4] = [7.784695709041462e-03, 3.224671290700398e-01, 2.445134137142996e+00,
+                         3.754408661907416e+00];
+    const P_LOW: f64 = 0.02425;
+
+    if p <= 0.0 {
+        return f64::NEG_INFINITY;
+    }
+    if p >= 1.0 {
+        return f64::INFINITY;
+    }
+
+    let tail = |q: f64| {
+        (((((C[0] * q + C[1]) * q + C[2]) * q + C[3]) * q + C[4]) * q + C[5]) /
+            ((((D[0] * q + D[1]) * q + D[2]) * q + D[3]) * q + 1.0)
+    };
+
+    if p < P_LOW {
+        tail((-2.0 * p.ln()).sqrt())
+    } else if p > 1.0 - P_LOW {
+        -tail((-2.0 * (1.0 - p).ln()).sqrt())
+    } else {
+        let q = p - 0.5;
+        let r = q * q;
+        (((((A[0] * r + A[1]) * r + A[2]) * r + A[3]) * r + A[4]) * r + A[5]) * q /
+            (((((B[0] * r + B[1]) * r + B[2]) * r + B[3]) * r + B[4]) * r + 1.0)
+    }
+}
+
+/// The quantile of the uniform distribution between 0 and 1 for the probability `p`
+pub fn uniform_quantile(p: f64) -> f64 {
+    p
+}
+
+/// The quantile of the exponential distribution with a rate of 1 for the probability `p`
+pub fn exponential_quantile(p: f64) -> f64 {
+    -(1.0 - p).ln()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-6, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_quantile() {
+        let values = sorted(&[4.0, 1.0, 3.0, 2.0, f64::NAN]);
+        assert_eq!(values, vec!(1.0, 2.0, 3.0, 4.0));
+
+        assert_eq!(quantile(&values, 0.0), Some(1.0));
+        assert_eq!(quantile(&values, 0.5), Some(2.5));
+        assert_eq!(quantile(&values, 1.0), Some(4.0));
+        assert_eq!(quantile(&[], 0.5), None);
+
+        assert_eq!(plotting_positions(4), vec!(0.125, 0.375, 0.625, 0.875));
+    }
+
+    #[test]
+    fn test_distributions() {
+        assert_close(normal_quantile(0.5), 0.0);
+        assert_close(normal_quantile(0.975), 1.959963985);
+        assert_close(normal_quantile(0.01), -2.326347874);
+        assert_close(normal_quantile(0.3), -normal_quantile(0.7));
+
+        assert_close(uniform_quantile(0.25), 0.25);
+        assert_close(exponential_quantile(0.5), 2f64.ln());
+    }
+}
@@ -0,0 +1,89 @@
+use pixel::Pixel;
+
+/// `TernaryDimensions` converts the three components of a composition to a
+/// `Pixel` inside an equilateral triangle centred on the canvas
+///
+/// A point made entirely of the first component is at the top corner, the
+/// second is at the bottom left and the third is at the bottom right
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TernaryDimensions {
+    pub height: f64,
+    pub width: f64,
+
+    pub border: f64,
+}
+
+impl TernaryDimensions {
+    pub fn new(width: f64, height: f64) -> TernaryDimensions {
+        TernaryDimensions {
+            height: height,
+            width: width,
+            border: 0.15,
+        }
+    }
+
+    /// The length in pixels of each side of the triangle
+    pub fn side(&self) -> f64 {
+        let height_ratio = 3f64.sqrt() / 2.0;
+        self.width.min(self.height / height_ratio) * (1.0 - 2.0 * self.border)
+    }
+
+    /// The top, bottom left and bottom right corners of the triangle
+    pub fn corners(&self) -> (Pixel, Pixel, Pixel) {
+        let side = self.side();
+        let triangle_height = side * 3f64.sqrt() / 2.0;
+        let left = (self.width - side) / 2.0;
+        let bottom = (self.height - triangle_height) / 2.0;
+
+        (Pixel::new(left + side / 2.0, bottom + triangle_height),
+         Pixel::new(left, bottom),
+         Pixel::new(left + side, bottom))
+    }
+
+    /// The centre of the triangle
+    pub fn centre(&self) -> Pixel {
+        let (top, left, right) = self.corners();
+        Pixel::new((top.x + left.x + right.x) / 3.0, (top.y + left.y + right.y) / 3.0)
+    }
+
+    /// Converts the components `a`, `b` and `c` to a `Pixel`, each component is taken as a
+    /// share of their sum, so points that don't quite add up to `total` are still placed
+    /// Returns `None` if any component is negative or isn't finite
+    pub fn convert_to_pixel(&self, a: f64, b: f64, c: f64) -> Option<Pixel> {
+        let sum = a + b + c;
+        if !(a >= 0.0 && b >= 0.0 && c >= 0.0 && sum > 0.0 && sum.is_finite()) {
+            return None;
+        }
+
+        let (top, left, right) = self.corners();
+        let (a, b, c) = (a / sum, b / sum, c / sum);
+        Some(Pixel::new(a * top.x + b * left.x + c * right.x, a * top.y + b * left.y + c * right.y))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pixel::Pixel;
+
+    #[test]
+    fn test_convert_to_pixel() {
+        let dims = TernaryDimensions::new(700.0, 600.0);
+        let (top, left, right) = dims.corners();
+
+        // the triangle should be equilateral
+        let side = dims.side();
+        assert!(((top.x - left.x).hypot(top.y - left.y) - side).abs() < 1e-9);
+        assert!(((right.x - left.x) - side).abs() < 1e-9);
+
+        assert_eq!(dims.convert_to_pixel(1.0, 0.0, 0.0), Some(top));
+        assert_eq!(dims.convert_to_pixel(0.0, 2.0, 0.0), Some(left));
+        assert_eq!(dims.convert_to_pixel(0.0, 0.5, 0.5), Some(Pixel::new(350.0, left.y)));
+
+        let centre = dims.convert_to_pixel(1.0, 1.0, 1.0).unwrap();
+        assert!((centre.x - dims.centre().x).abs() < 1e-9 && (centre.y - dims.centre().y).abs() < 1e-9);
+
+        assert_eq!(dims.convert_to_pixel(-0.1, 0.5, 0.6), None);
+        assert_eq!(dims.convert_to_pixel(0.0, 0.0, 0.0), None);
+    }
+}
@@ -0,0 +1,267 @@
+use options::{TernaryOptions, PointStyle};
+use plottable::{Plottable, Legend, LegendEntry};
+use plottable::primitives::Point;
+use labeller::{Labeller, Label};
+use pixel::{Color, GraphCoord, Pixel};
+use ternary_dimensions::TernaryDimensions;
+use graph_dimensions::GraphDimensions;
+use canvas::Canvas;
+
+// How far in pixels the tick labels and corner labels are from the triangle
+const TICK_OFFSET: f64 = 20.0;
+const LABEL_OFFSET: f64 = 30.0;
+
+/// How a `TernarySeries` is drawn
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TernaryStyle {
+    /// A marker at each point
+    Scatter,
+    /// A line through each point in order
+    Line,
+    /// A translucent region with the points as its outline
+    Fill,
+}
+
+/// `TernarySeries` holds compositions of three components to be
+/// drawn on a `TernaryGraph`
+#[derive(Clone, PartialEq)]
+pub struct TernarySeries<'a> {
+    pub points: &'a [(f64, f64, f64)],
+    pub style: TernaryStyle,
+    pub color: Color,
+    pub name: &'a str,
+    pub point_style: PointStyle,
+}
+
+impl <'a> TernarySeries<'a> {
+    pub fn new(points: &'a [(f64, f64, f64)], style: TernaryStyle) -> Self {
+        TernarySeries {
+            points: points,
+            style: style,
+            color: Color(0, 0, 255),
+            name: "",
+            point_style: PointStyle::Cross,
+        }
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// The name of the series in the legend
+    pub fn name(mut self, name: &'a str) -> Self {
+        self.name = name;
+        self
+    }
+
+    /// The marker used by a scatter series
+    pub fn point_style(mut self, point_style: PointStyle) -> Self {
+        self.point_style = point_style;
+        self
+    }
+}
+
+/// A `TernaryGraph` plots compositions of three components that add up to the
+/// same total inside of a triangle, each corner is where one component makes
+/// up the whole total and each edge is where one of them is missing
+pub struct TernaryGraph<'a, 'c, 'o, T: Canvas + 'c> {
+    pub series: Vec<&'a TernarySeries<'a>>,
+    canvas: &'c mut T,
+    pub dimensions: TernaryDimensions,
+    opts: &'o TernaryOptions<'o>,
+}
+
+impl <'a, 'c, 'o, T: Canvas> TernaryGraph<'a, 'c, 'o, T> {
+    pub fn new(canvas: &'c mut T, series: Vec<&'a TernarySeries<'a>>, opts: &'o TernaryOptions<'o>) -> Self {
+        let (width, height) = canvas.get_size();
+        let mut dimensions = TernaryDimensions::new(width, height);
+        dimensions.border = opts.border;
+
+        TernaryGraph {
+            series: series,
+            canvas: canvas,
+            dimensions: dimensions,
+            opts: opts,
+        }
+    }
+
+    pub fn add_series(&mut self, series: &'a TernarySeries<'a>) {
+        self.series.push(series);
+    }
+
+    fn ticks(&self) -> Label {
+        Labeller::in_base10().search(0.0, self.opts.total, self.opts.tick_count)
+    }
+
+    // The pixel at the given shares of the total
+    fn at(&self, a: f64, b: f64, c: f64) -> Option<Pixel> {
+        self.dimensions.convert_to_pixel(a, b, c)
+    }
+
+    // Moves `pix` `distance` pixels further away from the centre of the triangle
+    // along the direction from the centre to `towards`
+    fn away_from_centre(&self, pix: Pixel, towards: Pixel, distance: f64) -> Pixel {
+        let centre = self.dimensions.centre();
+        let (dx, dy) = (towards.x - centre.x, towards.y - centre.y);
+        let length = dx.hypot(dy).max(1e-9);
+        Pixel::new(pix.x + distance * dx / length, pix.y + distance * dy / length)
+    }
+
+    fn draw_grid(&mut self, ticks: Label) -> Result<(), T::Err> {
+        let total = self.opts.total;
+
+        // a gridline joins the two edges where one component is
+        // constant, each component's ticks go along its own edge
+        self.canvas.set_color(Color(200, 200, 200));
+        let mut tick = ticks.min;
+        while ticks.step > 0.0 && tick <= total * (1.0 + 1e-9) {
+            let (t, rest) = (tick, total - tick);
+            if t > 0.0 && rest > 0.0 {
+                let lines = [(self.at(t, rest, 0.0), self.at(t, 0.0, rest)),
+                             (self.at(0.0, t, rest), self.at(rest, t, 0.0)),
+                             (self.at(rest, 0.0, t), self.at(0.0, rest, t))];
+
+                for &(start, end) in lines.iter() {
+                    if let (Some(start), Some(end)) = (start, end) {
+                        self.canvas.draw_line(start, end)?;
+                    }
+                }
+            }
+            tick += ticks.step;
+        }
+
+        let (top, left, right) = self.dimensions.corners();
+        let middle = |a: Pixel, b: Pixel| Pixel::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0);
+
+        self.canvas.set_color(Color(0, 0, 0));
+        for &(a, b) in [(top, left), (left, right), (right, top)].iter() {
+            self.canvas.draw_line(a, b)?;
+        }
+
+        let mut tick = ticks.min;
+        while ticks.step > 0.0 && tick <= total * (1.0 + 1e-9) {
+            let rest = (total - tick).max(0.0);
+            let labels = [(self.at(tick, rest, 0.0), middle(top, left)),
+                          (self.at(0.0, tick, rest), middle(left, right)),
+                          (self.at(rest, 0.0, tick), middle(right, top))];
+
+            for &(pix, edge) in labels.iter() {
+                if let Some(pix) = pix {
+                    let label = self.away_from_centre(pix, edge, TICK_OFFSET);
+                    self.canvas.write_num_centred(tick, label)?;
+                }
+            }
+            tick += ticks.step;
+        }
+
+        let corners = [(top, self.opts.a_label), (left, self.opts.b_label), (right, self.opts.c_label)];
+        for &(corner, name) in corners.iter() {
+            let label = self.away_from_centre(corner, corner, LABEL_OFFSET);
+            self.canvas.write_text_centred(name, label)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn plot_series(&mut self, series: &TernarySeries) -> Result<(), T::Err> {
+        let pixels = series.points.iter()
+            .map(|&(a, b, c)| self.at(a, b, c))
+            .collect::<Vec<_>>();
+
+        self.canvas.set_color(series.color);
+        match series.style {
+            TernaryStyle::Scatter => {
+                for pix in pixels.into_iter().flatten() {
+                    Point::draw_marker(series.point_style, pix, self.canvas)?;
+                }
+            },
+            TernaryStyle::Line => {
+                for pair in pixels.windows(2) {
+                    if let (Some(start), Some(end)) = (pair[0], pair[1]) {
+                        self.canvas.draw_line(start, end)?;
+                    }
+                }
+            },
+            TernaryStyle::Fill => {
+                let outline = pixels.into_iter().flatten().collect::<Vec<_>>();
+
+                self.canvas.set_alpha(80);
+                let res = self.canvas.fill_polygon(&outline);
+                self.canvas.set_alpha(255);
+                res?;
+
+                for n in 0..outline.len() {
+                    self.canvas.draw_line(outline[n], outline[(n + 1) % outline.len()])?;
+                }
+            },
+        }
+
+        Ok(())
+    }
+
+    pub fn show(&mut self) -> Result<(), T::Err> {
+        self.canvas.set_color(Color(255, 255, 255));
+        self.canvas.clear();
+
+        let ticks = self.ticks();
+        self.draw_grid(ticks)?;
+
+        let series = self.series.clone();
+        for s in series.iter() {
+            self.plot_series(s)?;
+        }
+
+        // the legend goes in the top right corner of the canvas
+        let mut legend_bounds = GraphDimensions::new(self.dimensions.width, self.dimensions.height);
+        legend_bounds.min = GraphCoord::new(0.0, 0.0);
+        legend_bounds.max = GraphCoord::new(1.0, 1.0);
+        let entries = series.iter()
+            .filter(|s| !s.name.is_empty())
+            .map(|s| LegendEntry::new(s.name, s.color))
+            .collect::<Vec<_>>();
+        Legend(&[], &entries).plot(&legend_bounds, self.canvas)?;
+
+        let size = self.dimensions.width.min(self.dimensions.height);
+        let top = self.dimensions.height - (size * self.dimensions.border / 4.0);
+        self.canvas.set_color(Color(0, 0, 0));
+        self.canvas.write_text_centred(self.opts.label, (self.dimensions.width / 2.0, top))?;
+
+        self.canvas.show();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canvas::mock_canvas::MockCanvas;
+    use options::TernaryOptions;
+    use pixel::Color;
+
+    #[test]
+    fn test_show() {
+        let mut fake_canvas = MockCanvas::new();
+        let opts = TernaryOptions::default().total(100.0).a_label("Fe").b_label("Ni").c_label("Cr");
+
+        let alloys = [(70.0, 10.0, 20.0), (60.0, 25.0, 15.0), (50.0, 30.0, 20.0)];
+        let region = [(80.0, 0.0, 20.0), (60.0, 20.0, 20.0), (60.0, 0.0, 40.0)];
+        let scatter = TernarySeries::new(&alloys, TernaryStyle::Scatter).name("samples");
+        let line = TernarySeries::new(&alloys, TernaryStyle::Line).color(Color(255, 0, 0));
+        let fill = TernarySeries::new(&region, TernaryStyle::Fill).name("stainless");
+
+        {
+            let mut graph = TernaryGraph::new(&mut fake_canvas, vec!(&fill, &line), &opts);
+            graph.add_series(&scatter);
+
+            // the ticks should run from nothing to the whole total
+            let ticks = graph.ticks();
+            assert_eq!((ticks.min, ticks.max), (0.0, 100.0));
+
+            assert_eq!(graph.show(), Ok(()));
+        }
+
+        assert_eq!(fake_canvas.alpha, 255);
+        assert_eq!((fake_canvas.shown, fake_canvas.cleared), (1, 1));
+    }
+}
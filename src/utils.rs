@@ -1,4 +1,4 @@
-use pixel::GraphCoord;
+use pixel::{GraphCoord, Pixel};
 // use graph_dimensions::GraphDimensions;
 // use plottable::primitives::Line;
 use std::f64;
@@ -31,6 +31,152 @@ fn find_x_and_y_by_predicate<F, G>(init: G, coords: &[GraphCoord], f: F) -> Grap
     })
 }
 
+/// Linearly interpolates the y value at `x` along `points`, which must be sorted by x
+/// returns `None` if `x` is outside of the range covered by `points`
+pub fn interpolate_y(points: &[GraphCoord], x: f64) -> Option<f64> {
+    for pair in points.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        if x >= a.x && x <= b.x {
+            if b.x == a.x {
+                return Some(b.y);
+            }
+            let t = (x - a.x) / (b.x - a.x);
+            return Some(a.y + t * (b.y - a.y));
+        }
+    }
+
+    match points.first() {
+        Some(p) if points.len() == 1 && p.x == x => Some(p.y),
+        _ => None,
+    }
+}
+
+/// Splits a polygon into horizontal spans suitable for filling with lines,
+/// each span is returned as (y, x_start, x_end) and spans are one pixel apart
+pub fn scanline_spans(points: &[Pixel]) -> Vec<(f64, f64, f64)> {
+    let mut spans = Vec::new();
+    if points.len() < 3 {
+        return spans;
+    }
+
+    let min_y = points.iter().fold(f64::MAX, |acc, p| acc.min(p.y)).floor();
+    let max_y = points.iter().fold(f64::MIN, |acc, p| acc.max(p.y)).ceil();
+
+    let mut y = min_y;
+    while y < max_y {
+        // sample the middle of each row so vertices never sit exactly on the scanline
+        let scan_y = y + 0.5;
+        let mut crossings = Vec::new();
+
+        for i in 0..points.len() {
+            let a = points[i];
+            let b = points[(i + 1) % points.len()];
+
+            if (a.y <= scan_y && b.y > scan_y) || (b.y <= scan_y && a.y > scan_y) {
+                let t = (scan_y - a.y) / (b.y - a.y);
+                crossings.push(a.x + t * (b.x - a.x));
+            }
+        }
+
+        crossings.sort_by(|a, b| a.partial_cmp(b).unwrap_or(::std::cmp::Ordering::Equal));
+        for pair in crossings.chunks(2) {
+            if pair.len() == 2 {
+                spans.push((y, pair[0], pair[1]));
+            }
+        }
+
+        y += 1.0;
+    }
+
+    spans
+}
+
+/// Splits the cubic bezier curve from `p0` to `p3`, pulled towards `p1` and `p2`,
+/// into `segments` straight lines, returning the `segments + 1` points along it
+pub fn cubic_bezier(p0: Pixel, p1: Pixel, p2: Pixel, p3: Pixel, segments: usize) -> Vec<Pixel> {
+    let segments = segments.max(1);
+
+    (0..segments + 1).map(|n| {
+        let t = (n as f64) / (segments as f64);
+        let u = 1.0 - t;
+        let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+
+        Pixel::new(a * p0.x + b * p1.x + c * p2.x + d * p3.x, a * p0.y + b * p1.y + c * p2.y + d * p3.y)
+    }).collect()
+}
+
+/// Clips a polygon to the rectangle given by `min` and `max`
+/// using the Sutherland-Hodgman algorithm
+pub fn clip_polygon(points: &[GraphCoord], min: GraphCoord, max: GraphCoord) -> Vec<GraphCoord> {
+    let mut output = points.to_vec();
+
+    output = clip_edge(&output, |p| p.x >= min.x, |a, b| intersect_x(a, b, min.x));
+    output = clip_edge(&output, |p| p.x <= max.x, |a, b| intersect_x(a, b, max.x));
+    output = clip_edge(&output, |p| p.y >= min.y, |a, b| intersect_y(a, b, min.y));
+    output = clip_edge(&output, |p| p.y <= max.y, |a, b| intersect_y(a, b, max.y));
+
+    output
+}
+
+fn clip_edge<F, G>(points: &[GraphCoord], inside: F, intersect: G) -> Vec<GraphCoord>
+    where F: Fn(GraphCoord) -> bool,
+          G: Fn(GraphCoord, GraphCoord) -> GraphCoord {
+
+    let mut output = Vec::new();
+    if points.is_empty() {
+        return output;
+    }
+
+    let mut prev = points[points.len() - 1];
+    for &curr in points.iter() {
+        match (inside(prev), inside(curr)) {
+            (true, true) => output.push(curr),
+            (true, false) => output.push(intersect(prev, curr)),
+            (false, true) => {
+                output.push(intersect(prev, curr));
+                output.push(curr);
+            },
+            (false, false) => {},
+        }
+        prev = curr;
+    }
+
+    output
+}
+
+fn intersect_x(a: GraphCoord, b: GraphCoord, x: f64) -> GraphCoord {
+    let t = (x - a.x) / (b.x - a.x);
+    GraphCoord::new(x, a.y + t * (b.y - a.y))
+}
+
+fn intersect_y(a: GraphCoord, b: GraphCoord, y: f64) -> GraphCoord {
+    let t = (y - a.y) / (b.y - a.y);
+    GraphCoord::new(a.x + t * (b.x - a.x), y)
+}
+
+/// The layer of each of the `n` nodes joined by the directed `edges`, a node is one
+/// layer past the furthest node with an edge into it and nodes without any go in layer 0
+pub fn longest_path_layers(n: usize, edges: &[(usize, usize)]) -> Vec<usize> {
+    let mut layers = vec!(0; n);
+
+    // a cycle would keep pushing nodes along so it is stopped after the
+    // longest path that could exist without one
+    for _ in 0..n {
+        let mut changed = false;
+        for &(source, target) in edges.iter() {
+            if layers[target] < layers[source] + 1 && layers[source] + 1 < n {
+                layers[target] = layers[source] + 1;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+
+    layers
+}
+
 /*
 pub fn interp_to_grid(dims: &GraphDimensions, line: Line) -> Line {
     let start = line.0;
@@ -98,4 +244,73 @@ mod tests {
         assert_eq!(get_max_coord(coords), GraphCoord::new(f64::MIN, f64::MIN));
         assert_eq!(get_min_coord(coords), GraphCoord::new(f64::MAX, f64::MAX));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_interpolate_y() {
+        let points = &[GraphCoord::new(0.0, 0.0), GraphCoord::new(2.0, 4.0), GraphCoord::new(4.0, 0.0)];
+
+        assert_eq!(interpolate_y(points, 1.0), Some(2.0));
+        assert_eq!(interpolate_y(points, 2.0), Some(4.0));
+        assert_eq!(interpolate_y(points, 3.0), Some(2.0));
+
+        // nothing outside of the range of the points
+        assert_eq!(interpolate_y(points, -1.0), None);
+        assert_eq!(interpolate_y(points, 5.0), None);
+    }
+
+    #[test]
+    fn test_scanline_spans() {
+        let square = &[Pixel::new(0.0, 0.0), Pixel::new(10.0, 0.0), Pixel::new(10.0, 4.0), Pixel::new(0.0, 4.0)];
+        let spans = scanline_spans(square);
+
+        assert_eq!(spans.len(), 4);
+        assert_eq!(spans[0], (0.0, 0.0, 10.0));
+        assert_eq!(spans[3], (3.0, 0.0, 10.0));
+
+        // anything with fewer than 3 points has no area
+        assert_eq!(scanline_spans(&square[..2]), vec!());
+    }
+
+    #[test]
+    fn test_cubic_bezier() {
+        let (start, end) = (Pixel::new(0.0, 0.0), Pixel::new(10.0, 10.0));
+        let curve = cubic_bezier(start, Pixel::new(5.0, 0.0), Pixel::new(5.0, 10.0), end, 4);
+
+        // the curve starts and ends on the end points and is symmetric around the middle
+        assert_eq!(curve.len(), 5);
+        assert_eq!((curve[0], curve[4]), (start, end));
+        assert_eq!(curve[2], Pixel::new(5.0, 5.0));
+        assert!(curve[1].y < 2.5);
+    }
+
+    #[test]
+    fn test_clip_polygon() {
+        let min = GraphCoord::new(0.0, 0.0);
+        let max = GraphCoord::new(10.0, 10.0);
+
+        // fully inside should be unchanged
+        let inside = vec!(GraphCoord::new(1.0, 1.0), GraphCoord::new(5.0, 1.0), GraphCoord::new(5.0, 5.0));
+        assert_eq!(clip_polygon(&inside, min, max), inside);
+
+        // a square hanging off the right edge gets cut at x = 10
+        let square = &[GraphCoord::new(5.0, 2.0), GraphCoord::new(15.0, 2.0),
+                       GraphCoord::new(15.0, 8.0), GraphCoord::new(5.0, 8.0)];
+        let clipped = clip_polygon(square, min, max);
+        assert_eq!(clipped.len(), 4);
+        assert!(clipped.iter().all(|p| p.x <= 10.0));
+
+        // fully outside should disappear
+        let outside = &[GraphCoord::new(20.0, 20.0), GraphCoord::new(30.0, 20.0), GraphCoord::new(30.0, 30.0)];
+        assert_eq!(clip_polygon(outside, min, max), vec!());
+    }
+
+    #[test]
+    fn test_longest_path_layers() {
+        // a node goes past the longest path into it, not the shortest
+        assert_eq!(longest_path_layers(4, &[(0, 1), (1, 2), (0, 2), (3, 2)]), vec!(0, 1, 2, 0));
+
+        // a cycle should still finish with every node inside of the layers
+        let layers = longest_path_layers(3, &[(0, 1), (1, 2), (2, 0)]);
+        assert!(layers.iter().all(|&l| l < 3));
+    }
+}